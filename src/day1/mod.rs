@@ -1,21 +1,21 @@
 use crate::shared::*;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 pub struct Day1;
 
 impl Solution for Day1 {
-    fn part1(&self) -> Result<String> {
-        let lists = Lists::load("inputs/day1.txt")?.into_sorted();
+    type Input = Lists;
 
-        Ok(list_distance(&lists)?.to_string())
+    fn parse(&self, input: &str) -> Result<Lists> {
+        Lists::parse(input)
     }
 
-    fn part2(&self) -> Result<String> {
-        let lists = Lists::load("inputs/day1.txt")?;
+    fn part1(&self, lists: &Lists) -> Result<Answer> {
+        Ok(list_distance(&lists.clone().into_sorted())?.into())
+    }
 
-        Ok(calculate_similarity_score(&lists)?.to_string())
+    fn part2(&self, lists: &Lists) -> Result<Answer> {
+        Ok(calculate_similarity_score(lists)?.into())
     }
 }
 
@@ -55,21 +55,18 @@ fn calculate_similarity_score(lists: &Lists) -> Result<i32> {
     Ok(sum)
 }
 
-struct Lists {
+#[derive(Clone)]
+pub struct Lists {
     left: Vec<i32>,
     right: Vec<i32>,
 }
 
 impl Lists {
-    fn load(path: &str) -> Result<Lists> {
+    fn parse(input: &str) -> Result<Lists> {
         let mut left = Vec::new();
         let mut right = Vec::new();
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        for (i, line) in reader.lines().enumerate() {
-            let line = line?;
-
+        for (i, line) in input.lines().enumerate() {
             let parts: Vec<_> = line.split_whitespace().collect();
             if parts.len() != 2 {
                 return Err(Error::new(&format!(
@@ -105,7 +102,7 @@ struct SortedLists {
 #[test]
 fn test_day1_part1() {
     let r = list_distance(
-        &Lists::load("inputs/day1_example.txt")
+        &Lists::parse(&read_example("inputs/day1_example.txt"))
             .expect("failed to load example lists")
             .into_sorted(),
     )
@@ -115,7 +112,8 @@ fn test_day1_part1() {
 
 #[test]
 fn test_day1_part2() {
-    let lists = Lists::load("inputs/day1_example.txt").expect("failed to load example lists");
+    let lists = Lists::parse(&read_example("inputs/day1_example.txt"))
+        .expect("failed to load example lists");
 
     let score = calculate_similarity_score(&lists).expect("failed to calculate similarity score");
 
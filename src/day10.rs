@@ -1,117 +1,94 @@
 use crate::shared::*;
-use std::collections::HashSet;
 
 pub struct Day10;
 
 impl Solution for Day10 {
-    fn part1(&self) -> Result<String> {
-        part1("inputs/day10.txt").map(|v| v.to_string())
+    type Input = Grid<u8>;
+
+    fn parse(&self, input: &str) -> Result<Grid<u8>> {
+        parse_map(input)
+    }
+
+    fn part1(&self, map: &Grid<u8>) -> Result<Answer> {
+        Ok(part1(map).into())
     }
 
-    fn part2(&self) -> Result<String> {
-        part2("inputs/day10.txt").map(|v| v.to_string())
+    fn part2(&self, map: &Grid<u8>) -> Result<Answer> {
+        Ok(part2(map).into())
     }
 }
 
-fn part1(path: &str) -> Result<usize> {
-    let cg = CharGrid::from_file(path)?;
+// The topographic map, as the height of each position.
+fn parse_map(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, |c| match c.to_digit(10) {
+        Some(height) => Ok(height as u8),
+        None => Err(Error::new(&format!("unexpected character in map: {}", c))),
+    })
+}
 
+fn part1(map: &Grid<u8>) -> usize {
     let mut sum = 0;
-    for trailhead in cg.find_all_pos('0') {
-        sum += find_peaks(&cg, trailhead);
+    for trailhead in map.find_all_pos(0) {
+        sum += find_peaks(map, trailhead);
     }
 
-    Ok(sum)
+    sum
 }
 
 #[test]
 fn test_part1() {
-    let result = part1("inputs/day10_example.txt").unwrap();
+    let map = parse_map(&read_example("inputs/day10_example.txt")).unwrap();
+    let result = part1(&map);
 
     assert_eq!(36, result);
 }
 
-fn find_peaks(cg: &CharGrid, origin: Position) -> usize {
-    let mut searched = HashSet::new();
-    let mut frontier = vec![origin];
-
-    let mut found_peaks = 0;
-
-    while !frontier.is_empty() {
-        let loc = frontier.pop().unwrap();
-        debug_assert!(!searched.contains(&loc));
-
-        searched.insert(loc);
-
-        let this = cg.at(loc.x, loc.y);
-
-        match this {
-            '9' => found_peaks += 1,
-            '0'..='8' => {
-                for candidate in [loc + UP, loc + DOWN, loc + LEFT, loc + RIGHT] {
-                    if cg.in_bounds(candidate)
-                        && !searched.contains(&candidate)
-                        && !frontier.contains(&candidate)
-                        && cg.at(candidate.x, candidate.y) as u32 == this as u32 + 1
-                    {
-                        frontier.push(candidate);
-                    }
-                }
-            }
-            _ => panic!("unexpected character in map: {}", this),
-        }
-    }
+fn find_peaks(map: &Grid<u8>, origin: Position) -> usize {
+    bfs_all(origin, |p| uphill(map, *p), |p| map.at_pos(*p) == 9)
+        .goals()
+        .len()
+}
 
-    found_peaks
+fn uphill(map: &Grid<u8>, loc: Position) -> impl Iterator<Item = Position> + '_ {
+    let this = map.at_pos(loc);
+    map.neighbours(loc, ORTHOGONAL)
+        .filter(move |(_, height)| **height == this + 1)
+        .map(|(p, _)| p)
 }
 
 #[test]
 fn test_find_peaks() {
-    let cg = CharGrid::from_file("inputs/day10_example.txt").unwrap();
+    let map = parse_map(&read_example("inputs/day10_example.txt")).unwrap();
 
-    assert_eq!(5, find_peaks(&cg, Position { x: 2, y: 0 }))
+    assert_eq!(5, find_peaks(&map, Position { x: 2, y: 0 }))
 }
 
-fn rate_trailhead(cg: &CharGrid, loc: Position) -> usize {
-    let this = cg.at(loc.x, loc.y);
-
-    if this == '9' {
-        1
-    } else {
-        let mut sum = 0;
-
-        for candidate in [loc + UP, loc + DOWN, loc + LEFT, loc + RIGHT] {
-            if cg.in_bounds(candidate) && cg.at(candidate.x, candidate.y) as u32 == this as u32 + 1
-            {
-                sum += rate_trailhead(&cg, candidate);
-            }
-        }
-
-        sum
-    }
+// Every trail climbs one step at a time so has the same length, which makes each distinct trail
+// to a peak a shortest path.
+fn rate_trailhead(map: &Grid<u8>, loc: Position) -> usize {
+    bfs_all(loc, |p| uphill(map, *p), |p| map.at_pos(*p) == 9).count_paths()
 }
 
 #[test]
 fn test_rate_trailhead() {
-    let cg = CharGrid::from_file("inputs/day10_example.txt").unwrap();
+    let map = parse_map(&read_example("inputs/day10_example.txt")).unwrap();
 
-    assert_eq!(20, rate_trailhead(&cg, Position { x: 2, y: 0 }));
+    assert_eq!(20, rate_trailhead(&map, Position { x: 2, y: 0 }));
 }
 
-fn part2(path: &str) -> Result<usize> {
-    let cg = CharGrid::from_file(path)?;
-
+fn part2(map: &Grid<u8>) -> usize {
     let mut sum = 0;
-    for trailhead in cg.find_all_pos('0') {
-        sum += rate_trailhead(&cg, trailhead);
+    for trailhead in map.find_all_pos(0) {
+        sum += rate_trailhead(map, trailhead);
     }
 
-    Ok(sum)
+    sum
 }
 
 #[test]
 fn test_part2() {
-    let result = part2("inputs/day10_example.txt").unwrap();
+    let map = parse_map(&read_example("inputs/day10_example.txt")).unwrap();
+    let result = part2(&map);
 
     assert_eq!(81, result);
 }
@@ -1,24 +1,26 @@
 use crate::shared::*;
 use std::collections::HashMap;
-use std::fs::read_to_string;
-use std::time::Instant;
 
 pub struct Day11;
 
 impl Solution for Day11 {
-    fn part1(&self) -> Result<String> {
-        let stones = read_stones("inputs/day11.txt")?;
-        Ok(stoneify(stones, 25).count().to_string())
+    type Input = Stones;
+
+    fn parse(&self, input: &str) -> Result<Stones> {
+        parse_stones(input)
+    }
+
+    fn part1(&self, stones: &Stones) -> Result<Answer> {
+        Ok(stoneify(stones.clone(), 25).count().into())
     }
 
-    fn part2(&self) -> Result<String> {
-        let stones = read_stones("inputs/day11.txt")?;
-        Ok(stoneify(stones, 75).count().to_string())
+    fn part2(&self, stones: &Stones) -> Result<Answer> {
+        Ok(stoneify(stones.clone(), 75).count().into())
     }
 }
 
-fn read_stones(path: &str) -> Result<Stones> {
-    let values = read_to_string(path)?
+fn parse_stones(input: &str) -> Result<Stones> {
+    let values = input
         .split_whitespace()
         .map(|n| n.parse::<usize>())
         .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -31,7 +33,8 @@ fn read_stones(path: &str) -> Result<Stones> {
     Ok(stones)
 }
 
-struct Stones {
+#[derive(Clone)]
+pub struct Stones {
     ns: HashMap<usize, usize>,
 }
 
@@ -78,14 +81,12 @@ impl Stones {
 
 fn stoneify(mut stones: Stones, iters: usize) -> Stones {
     for i in 0..iters {
-        let start = Instant::now();
         let start_count = stones.count();
         stones = stones.run_step();
 
-        println!(
-            "iteration {} complete in {}ms (-> {} stones [+ {}]) (seen {} unique numbers)",
+        debug!(
+            "iteration {} complete (-> {} stones [+ {}]) (seen {} unique numbers)",
             i,
-            start.elapsed().as_millis(),
             stones.count(),
             stones.count() - start_count,
             stones.uniq_count()
@@ -101,7 +102,7 @@ fn is_even(n: usize) -> bool {
 
 #[test]
 fn test_part1() {
-    let ns = read_stones("inputs/day11_example.txt").unwrap();
+    let ns = parse_stones(&read_example("inputs/day11_example.txt")).unwrap();
     let result = stoneify(ns, 25);
 
     assert_eq!(55312, result.count());
@@ -2,22 +2,34 @@ use crate::shared::*;
 use nalgebra::{Matrix2, Vector2, QR};
 use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::fs::read_to_string;
 
 pub struct Day13;
 
 impl Solution for Day13 {
-    fn part1(&self) -> Result<String> {
-        part1("inputs/day13.txt").map(|v| v.to_string())
+    type Input = Vec<Scenario>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Scenario>> {
+        parse_scenarios(input)
+    }
+
+    fn part1(&self, scenarios: &Vec<Scenario>) -> Result<Answer> {
+        Ok(part1(scenarios).into())
     }
 
-    fn part2(&self) -> Result<String> {
-        part2("inputs/day13.txt").map(|v| v.to_string())
+    fn part2(&self, scenarios: &Vec<Scenario>) -> Result<Answer> {
+        Ok(part2(scenarios).into())
     }
 }
 
+fn parse_scenarios(input: &str) -> Result<Vec<Scenario>> {
+    split_docs(input)
+        .iter()
+        .map(|doc| Scenario::from_str(doc))
+        .collect()
+}
+
 #[derive(Copy, Clone, Debug)]
-struct Scenario {
+pub struct Scenario {
     a_x: usize,
     a_y: usize,
 
@@ -118,7 +130,7 @@ fn solve(scenario: Scenario) -> Option<Plan> {
         }
     }
 
-    println!("found cheapest={:?} after {} iterations", cheapest, iter_ct);
+    debug!("found cheapest={:?} after {} iterations", cheapest, iter_ct);
 
     cheapest
 }
@@ -130,8 +142,8 @@ fn solve_with_math(s: Scenario) -> Option<Plan> {
     let c = QR::new(terms);
 
     let solution = c.solve(&Vector2::new(s.prize_x as f64, s.prize_y as f64));
-    if solution.is_some() {
-        println!("{}", &solution.unwrap());
+    if let Some(solution) = &solution {
+        trace!("solution={}", solution);
     }
 
     match solution {
@@ -139,7 +151,7 @@ fn solve_with_math(s: Scenario) -> Option<Plan> {
             let a = solution[0].round();
             let b = solution[1].round();
 
-            println!("a={}, b={}", a, b);
+            trace!("a={}, b={}", a, b);
             // determine if our solution is made of integers by confirming the solution with
             // rounded results
             let plan = Plan {
@@ -197,26 +209,25 @@ fn test_unsolvable() {
     assert_eq!(None, solve_with_math(scenario));
 }
 
-fn part1(path: &str) -> Result<usize> {
+fn part1(scenarios: &[Scenario]) -> usize {
     let mut sum = 0;
 
-    for doc in split_docs(read_to_string(path)?) {
-        let scenario = Scenario::from_str(&doc)?;
-        dbg!(&scenario);
+    for scenario in scenarios {
+        debug!("{:?}", scenario);
 
-        if let Some(plan) = solve(scenario) {
+        if let Some(plan) = solve(*scenario) {
             sum += plan.cost();
         }
     }
 
-    Ok(sum)
+    sum
 }
 
-fn part2(path: &str) -> Result<usize> {
+fn part2(scenarios: &[Scenario]) -> usize {
     let mut sum = 0;
 
-    for doc in split_docs(read_to_string(path)?) {
-        let mut scenario = Scenario::from_str(&doc)?;
+    for scenario in scenarios {
+        let mut scenario = *scenario;
 
         scenario.prize_x += 10000000000000;
         scenario.prize_y += 10000000000000;
@@ -226,10 +237,12 @@ fn part2(path: &str) -> Result<usize> {
         }
     }
 
-    Ok(sum)
+    sum
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(Ok(480), part1("inputs/day13_example.txt"));
+    let scenarios = parse_scenarios(&read_example("inputs/day13_example.txt")).unwrap();
+
+    assert_eq!(480, part1(&scenarios));
 }
@@ -1,26 +1,45 @@
 use crate::shared::*;
 use regex::Regex;
 use std::collections::HashSet;
-use std::fs::read_to_string;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 pub struct Day14;
 
 impl Solution for Day14 {
-    fn part1(&self) -> Result<String> {
-        part1("inputs/day14.txt", Dimensions::of(101, 103)).map(|v| v.to_string())
+    type Input = Robots;
+
+    fn parse(&self, input: &str) -> Result<Robots> {
+        Robots::from_str(input, Dimensions::of(101, 103))
+    }
+
+    fn part1(&self, robots: &Robots) -> Result<Answer> {
+        Ok(part1(robots.clone()).into())
+    }
+
+    fn part2(&self, robots: &Robots) -> Result<Answer> {
+        part2(robots.clone()).map(Answer::from)
     }
 
-    fn part2(&self) -> Result<String> {
-        part2("inputs/day14.txt", Dimensions::of(101, 103)).map(|v| v.to_string())
+    // The last 20 steps up to step 100 for part 1, or up to the tree for part 2. Every frame is
+    // stored uncompressed, so they aren't all kept.
+    fn frames(&self, part: u32, robots: &Robots) -> Result<Frames> {
+        let steps = match part {
+            1 => 100,
+            _ => part2(robots.clone())?,
+        };
+
+        Ok(robots.clone().frames(steps, 20))
     }
 }
 
+#[derive(Clone, PartialEq, Eq)]
 struct Robot {
     pos: Position,
     vel: Direction,
 }
 
-struct Robots {
+#[derive(Clone, PartialEq, Eq)]
+pub struct Robots {
     robots: Vec<Robot>,
     bounds: Dimensions,
 }
@@ -56,13 +75,17 @@ impl Robots {
     }
 
     fn advance_p1(&mut self) {
-        let bounds = (Position::at(0, 0), self.bounds);
-
         for robot in &mut self.robots {
-            robot.pos = robot.pos.wrapping_add_direction(robot.vel, bounds);
+            robot.pos = Topology::Torus.wrap(robot.pos + robot.vel, self.bounds).unwrap();
         }
     }
 
+    fn advanced(&self) -> Robots {
+        let mut robots = self.clone();
+        robots.advance_p1();
+        robots
+    }
+
     fn safety_factor(&mut self) -> usize {
         let mid_x = self.bounds.w / 2;
         let mid_y = self.bounds.h / 2;
@@ -81,43 +104,81 @@ impl Robots {
             }
         }
 
-        self.draw();
-        println!("quadrant_count = {:?}", quadrant_count);
+        debug!("{}", self.draw());
+        debug!("quadrant_count = {:?}", quadrant_count);
 
         quadrant_count[0] * quadrant_count[1] * quadrant_count[2] * quadrant_count[3]
     }
 
-    fn draw(&self) {
-        let mut cg = CharGrid::new(self.bounds.w, self.bounds.h);
-
+    fn draw(&self) -> String {
         let mid_x = self.bounds.w / 2;
         let mid_y = self.bounds.h / 2;
 
-        for y in 0..cg.height() {
-            for x in 0..cg.width() {
-                if x == mid_x || y == mid_y {
-                    cg.set(x, y, ' ')
-                } else {
-                    cg.set(x, y, '.')
-                }
-            }
-        }
+        // Robots on the middle lines aren't in any quadrant, so they're left out.
+        let plane = self.plane();
+        let counts = plane
+            .iter()
+            .filter(|(p, _)| p.x != mid_x && p.y != mid_y)
+            .map(|(p, c)| (p, *c));
+
+        Renderer::from_fn(self.bounds, |p| match p.x == mid_x || p.y == mid_y {
+            true => ' ',
+            false => '.',
+        })
+        .label(
+            "robots",
+            Color::Yellow,
+            counts,
+        )
+        .render()
+    }
 
+    // The number of robots on each occupied cell, drawn the way the puzzle does with + for more
+    // than nine.
+    fn plane(&self) -> SparseGrid<char> {
+        let mut plane = SparseGrid::new('.');
         for robot in &self.robots {
-            let c = match cg.at(robot.pos.x, robot.pos.y) {
-                ' ' => ' ',
+            let count = match *plane.get(robot.pos) {
                 '.' => '1',
-                '1' => '2',
-                '2' => '3',
-                '3' => '4',
-                '4' => '5',
-                _ => 'n',
+                '9' | '+' => '+',
+                c => (c as u8 + 1) as char,
             };
+            plane.set(robot.pos, count);
+        }
+
+        plane
+    }
+
+    fn image(&self) -> Image {
+        let mut grid = Grid::from_fn(self.bounds.w, self.bounds.h, |_, _| false);
+        for robot in &self.robots {
+            grid.set_pos(robot.pos, true);
+        }
+
+        Image::from_grid(&grid, 4, |r| if *r { Rgb(0, 160, 0) } else { BLACK })
+    }
+
+    // The images of the last keep steps up to and including the given one.
+    fn frames(mut self, steps: usize, keep: usize) -> Frames {
+        let mut frames = Frames::default();
+        for i in 0..=steps {
+            if i + keep > steps {
+                frames.push(self.image());
+            }
+            self.advance_p1();
+        }
+
+        frames
+    }
 
-            cg.set(robot.pos.x, robot.pos.y, c)
+    // Identifies the robots' positions without keeping a copy of them.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for robot in &self.robots {
+            robot.pos.hash(&mut hasher);
         }
 
-        println!("{}", cg.draw())
+        hasher.finish()
     }
 
     fn all_unique_positions(&self) -> bool {
@@ -135,35 +196,61 @@ impl Robots {
     }
 }
 
-fn part1(path: &str, bounds: Dimensions) -> Result<usize> {
-    let mut robots = Robots::from_str(&read_to_string(path)?, bounds)?;
-
+fn part1(mut robots: Robots) -> usize {
     for _ in 0..100 {
         robots.advance_p1();
     }
 
-    Ok(robots.safety_factor())
+    robots.safety_factor()
 }
 
-fn part2(path: &str, bounds: Dimensions) -> Result<usize> {
-    let mut robots = Robots::from_str(&read_to_string(path)?, bounds)?;
+fn part2(mut robots: Robots) -> Result<usize> {
+    let start = robots.clone();
+
+    // The robots come back round to a state they've been in before eventually, and if they get
+    // that far without all being apart there's no tree to find.
+    let mut detector = CycleDetector::default();
+
+    for i in 0.. {
+        if let Some(cycle) = detector.observe(robots.fingerprint()) {
+            // Fingerprints can collide, so check the robots really are back where they were.
+            let seen = state_at(start.clone(), |r| Some(r.advanced()), cycle.prefix);
+            if seen.as_ref() == Some(&robots) {
+                return Err(Error::new(&format!(
+                    "no stable state found before the robots repeat every {} iters",
+                    cycle.period
+                )));
+            }
+        }
 
-    for i in 0..10000 {
         robots.advance_p1();
+
         if robots.all_unique_positions() {
-            robots.draw();
-            println!("unique at {} iters", i);
+            debug!("{}", robots.plane().draw());
+            info!("unique at {} iters", i);
+
             return Ok(i + 1);
         }
     }
 
-    Err(Error::new("no stable state found after 10000 iters"))
+    unreachable!()
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(
-        12,
-        part1("inputs/day14_example.txt", Dimensions::of(11, 7)).unwrap()
+    let robots = Robots::from_str(
+        &read_example("inputs/day14_example.txt"),
+        Dimensions::of(11, 7),
     )
+    .unwrap();
+
+    assert_eq!(12, part1(robots))
+}
+
+#[test]
+fn test_part2_no_tree() {
+    // Two robots moving together are never apart, so they only go round in circles.
+    let robots = Robots::from_str("p=0,0 v=1,2\np=0,0 v=1,2\n", Dimensions::of(11, 7)).unwrap();
+
+    assert!(part2(robots).is_err());
 }
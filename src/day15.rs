@@ -1,32 +1,52 @@
 use crate::shared::*;
-use std::fs::read_to_string;
 
 pub struct Day15;
 
 impl Solution for Day15 {
-    fn part1(&self) -> Result<String> {
-        solve("inputs/day15.txt", false).map(|v| v.to_string())
+    type Input = Warehouse;
+
+    fn parse(&self, input: &str) -> Result<Warehouse> {
+        Warehouse::from_str(input)
+    }
+
+    fn part1(&self, warehouse: &Warehouse) -> Result<Answer> {
+        solve(warehouse, false).map(Answer::from)
     }
 
-    fn part2(&self) -> Result<String> {
-        solve("inputs/day15.txt", true).map(|v| v.to_string())
+    fn part2(&self, warehouse: &Warehouse) -> Result<Answer> {
+        solve(warehouse, true).map(Answer::from)
     }
 }
 
-fn solve(path: &str, expand: bool) -> Result<usize> {
-    let contents = read_to_string(path)?;
-    let docs = split_docs(contents);
-    debug_assert!(docs.len() == 2);
+pub struct Warehouse {
+    map: CharGrid,
+    commands: String,
+}
+
+impl Warehouse {
+    fn from_str(input: &str) -> Result<Warehouse> {
+        let docs = split_docs(input);
+        if docs.len() != 2 {
+            return Err(Error::new("expected a map and a list of commands"));
+        }
+
+        Ok(Warehouse {
+            map: CharGrid::from_str(&docs[0])?,
+            commands: docs[1].clone(),
+        })
+    }
+}
 
-    let mut cg = CharGrid::from_str(&docs[0])?;
+fn solve(warehouse: &Warehouse, expand: bool) -> Result<usize> {
+    let mut cg = warehouse.map.clone();
     if expand {
-        cg = expand_map(cg);
+        cg = expand_map(cg)?;
     }
 
     let (x, y) = cg.find_one('@')?;
     let mut pos = Position::at(x, y);
 
-    for command in docs[1].bytes() {
+    for command in warehouse.commands.bytes() {
         if command == '\n' as u8 {
             continue;
         }
@@ -56,14 +76,19 @@ fn solve(path: &str, expand: bool) -> Result<usize> {
         .sum())
 }
 
+#[cfg(test)]
+fn example() -> Warehouse {
+    Warehouse::from_str(&read_example("inputs/day15_example.txt")).unwrap()
+}
+
 #[test]
 fn test_part1() {
-    assert_eq!(10092, solve("inputs/day15_example.txt", false).unwrap());
+    assert_eq!(10092, solve(&example(), false).unwrap());
 }
 
 #[test]
 fn test_part2() {
-    assert_eq!(9021, solve("inputs/day15_example.txt", true).unwrap())
+    assert_eq!(9021, solve(&example(), true).unwrap())
 }
 
 fn cg_move(cg: &mut CharGrid, pos: Position, dir: Direction) -> bool {
@@ -140,25 +165,14 @@ fn test_part2_move() {
     );
 }
 
-fn expand_map(cg: CharGrid) -> CharGrid {
-    let mut target = CharGrid::new(cg.width() * 2, cg.height());
-
-    for y in 0..cg.height() {
-        for x in 0..cg.width() {
-            let chars = match cg.at(x, y) {
-                '#' => ('#', '#'),
-                '.' => ('.', '.'),
-                'O' => ('[', ']'),
-                '@' => ('@', '.'),
-                v => panic!("unexpected char '{}'", v),
-            };
-
-            target.set(x * 2, y, chars.0);
-            target.set(x * 2 + 1, y, chars.1);
-        }
-    }
-
-    target
+fn expand_map(cg: CharGrid) -> Result<CharGrid> {
+    cg.expand(2, 1, |c| match c {
+        '#' => Ok(vec!['#', '#']),
+        '.' => Ok(vec!['.', '.']),
+        'O' => Ok(vec!['[', ']']),
+        '@' => Ok(vec!['@', '.']),
+        v => Err(Error::new(&format!("unexpected char '{}'", v))),
+    })
 }
 
 #[test]
@@ -174,7 +188,7 @@ fn test_expand_map() {
     )
     .unwrap();
 
-    let expanded = expand_map(cg);
+    let expanded = expand_map(cg).unwrap();
 
     assert_eq!(
         expanded.draw(),
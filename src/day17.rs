@@ -1,24 +1,25 @@
 use crate::shared::*;
 use getch_rs::{Getch, Key};
-use std::time::Instant;
 
 pub struct Day17;
 
 impl Solution for Day17 {
-    fn part1(&self) -> Result<String> {
-        let mut cpu = CPU::new_with_input();
+    type Input = CPU;
+
+    fn parse(&self, input: &str) -> Result<CPU> {
+        CPU::from_str(input)
+    }
+
+    fn part1(&self, cpu: &CPU) -> Result<Answer> {
+        let mut cpu = cpu.clone();
 
         let output = cpu.execute();
 
-        Ok(output
-            .iter()
-            .map(|v| v.to_string())
-            .collect::<Vec<_>>()
-            .join(","))
+        Ok(output.into())
     }
 
-    fn part2(&self) -> Result<String> {
-        let mut cpu = CPU::new_with_input();
+    fn part2(&self, cpu: &CPU) -> Result<Answer> {
+        let mut cpu = cpu.clone();
 
         let mut a = 0;
         let g = Getch::new();
@@ -78,7 +79,8 @@ fn op_name(op: u64) -> &'static str {
     }
 }
 
-struct CPU {
+#[derive(Clone)]
+pub struct CPU {
     pc: u64,
 
     ram: Vec<u64>,
@@ -102,28 +104,43 @@ impl CPU {
         }
     }
 
-    fn new_with_input() -> Self {
-        let mut cpu = CPU {
-            pc: 0,
-            ram: Vec::new(),
-            a: 30118712,
-            b: 0,
-            c: 0,
-            quine_compare_mode: false,
+    // Parses the register and program listing from the puzzle input. For reference, my input's
+    // program decodes to:
+    //
+    //   2, 4, // B = A % 8
+    //   1, 3, // B = B ^ 3
+    //   7, 5, // C = A / 2^B
+    //   4, 2, // B = B ^ C
+    //   0, 3, // A = A / 2^3 (8)
+    //   1, 5, // B = B ^ 5
+    //   5, 5, // OUT B
+    //   3, 0, // IF (A != 0) GOTO 0
+    fn from_str(input: &str) -> Result<Self> {
+        let rx = regex::Regex::new(
+            r"Register A: (\d+)\s+Register B: (\d+)\s+Register C: (\d+)\s+Program: ([\d,]+)",
+        )?;
+
+        let c = match rx.captures(input) {
+            None => return Err(Error::new("failed to parse cpu state")),
+            Some(c) => c,
         };
 
-        cpu.load_program(&[
-            2, 4, // B = A % 8
-            1, 3, // B = B ^ 3
-            7, 5, // C = A / 2^B
-            4, 2, // B = B ^ C
-            0, 3, // A = A / 2^3 (8)
-            1, 5, // B = B ^ 5
-            5, 5, // OUT B
-            3, 0, // IF (A != 0) GOTO 0
-        ]);
-
-        cpu
+        let mut cpu = CPU::new(
+            c.get(1).unwrap().as_str().parse::<u64>()?,
+            c.get(2).unwrap().as_str().parse::<u64>()?,
+            c.get(3).unwrap().as_str().parse::<u64>()?,
+        );
+
+        let program = c
+            .get(4)
+            .unwrap()
+            .as_str()
+            .split(",")
+            .map(|v| v.parse::<u64>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        cpu.load_program(&program);
+
+        Ok(cpu)
     }
 
     fn load_program(&mut self, prog: &[u64]) {
@@ -215,10 +232,8 @@ impl CPU {
     }
 
     fn find_quine(&mut self) -> u64 {
-        let start = Instant::now();
-
         let (low, high) = self.find_quine_range();
-        println!(
+        info!(
             "searching from {} to {} ({} values) for value",
             low,
             high,
@@ -236,9 +251,8 @@ impl CPU {
             }
 
             if a % 100 == 0 {
-                println!(
-                    "[{a}] elapsed={}ms pct={:.2} output_len={}, output={:?}",
-                    start.elapsed().as_millis(),
+                trace!(
+                    "[{a}] pct={:.2} output_len={}, output={:?}",
                     (a as f64 / u64::MAX as f64) * 100f64,
                     output.len(),
                     output
@@ -257,6 +271,21 @@ impl CPU {
     }
 }
 
+#[test]
+fn test_from_str() {
+    let cpu = CPU::from_str(
+        "Register A: 729\n\
+         Register B: 0\n\
+         Register C: 0\n\
+         \n\
+         Program: 0,1,5,4,3,0\n",
+    )
+    .unwrap();
+
+    assert_eq!(729, cpu.a);
+    assert_eq!(vec![0, 1, 5, 4, 3, 0], cpu.ram);
+}
+
 #[test]
 fn test_part1() {
     let mut cpu = CPU::new(729, 0, 0);
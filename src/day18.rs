@@ -1,40 +1,37 @@
 use crate::shared::*;
-use std::collections::{HashSet, VecDeque};
-use std::fs::read_to_string;
 
 pub struct Day18;
 
 impl Solution for Day18 {
-    fn part1(&self) -> Result<String> {
-        let bytes = Bytes::from_file("inputs/day18.txt")?;
+    type Input = Bytes;
 
+    fn parse(&self, input: &str) -> Result<Bytes> {
+        Bytes::from_str(input)
+    }
+
+    fn part1(&self, bytes: &Bytes) -> Result<Answer> {
         part1(
             Dimensions::of(71, 71),
             Position::at(70, 70),
             &bytes.first(1024),
         )
-        .map(|v| v.to_string())
+        .map(Answer::from)
     }
 
-    fn part2(&self) -> Result<String> {
-        let bytes = Bytes::from_file("inputs/day18.txt")?;
-
-        let pos = part2(Dimensions::of(71, 71), Position::at(70, 70), &bytes)?;
-
-        Ok(format!("{},{}", pos.x, pos.y))
+    fn part2(&self, bytes: &Bytes) -> Result<Answer> {
+        part2(Dimensions::of(71, 71), Position::at(70, 70), bytes).map(Answer::from)
     }
 }
 
-struct Bytes {
+pub struct Bytes {
     positions: Vec<Position>,
 }
 
 impl Bytes {
-    fn from_file(path: &str) -> Result<Bytes> {
-        let contents = read_to_string(path)?;
+    fn from_str(input: &str) -> Result<Bytes> {
         let mut positions = Vec::new();
 
-        for line in contents.lines() {
+        for line in input.lines() {
             let parts = line.split(",").collect::<Vec<_>>();
             debug_assert!(parts.len() == 2);
 
@@ -49,69 +46,79 @@ impl Bytes {
         })
     }
 
-    fn first(self, n: usize) -> Bytes {
+    fn first(&self, n: usize) -> Bytes {
         Bytes {
-            positions: self.positions.into_iter().take(n).collect(),
+            positions: self.positions.iter().take(n).copied().collect(),
         }
     }
 }
 
-fn shortest_path(cg: &CharGrid, start: Position, end: Position) -> Option<usize> {
-    let mut queue = VecDeque::new();
-    let mut explored = HashSet::new();
-    explored.insert(start);
-    queue.push_front((0, start));
+fn shortest_path(cg: &CharGrid, start: Position, end: Position) -> Search<Position> {
+    astar(
+        start,
+        |pos| {
+            cg.neighbours(*pos, ORTHOGONAL)
+                .filter(|(_, c)| **c == '.')
+                .map(|(p, _)| (p, 1))
+        },
+        |pos| ((end.x - pos.x).abs() + (end.y - pos.y).abs()) as usize,
+        |pos| *pos == end,
+    )
+}
 
-    while !queue.is_empty() {
-        let (dist, pos) = queue.pop_back().unwrap();
-        if pos == end {
-            return Some(dist);
-        }
+// The memory space with the given bytes fallen into it.
+fn fallen(dims: Dimensions, positions: &[Position]) -> CharGrid {
+    let mut cg = CharGrid::new(dims.w, dims.h);
+    cg.fill('.');
 
-        for dir in [UP, DOWN, LEFT, RIGHT] {
-            let target = pos + dir;
-            if cg.in_bounds(target) && cg.at_pos(target) == '.' && !explored.contains(&target) {
-                explored.insert(target);
-                queue.push_front((dist + 1, target));
-            }
-        }
+    for pos in positions {
+        cg.set_pos(*pos, '#');
     }
 
-    None
+    cg
 }
 
 fn part1(dims: Dimensions, goal: Position, bytes: &Bytes) -> Result<usize> {
-    let mut cg = CharGrid::new(dims.w, dims.h);
-    cg.fill('.');
-
-    for pos in &bytes.positions {
-        cg.set_pos(*pos, '#');
+    let cg = fallen(dims, &bytes.positions);
+
+    let search = shortest_path(&cg, Position::at(0, 0), goal);
+    if log::enabled(log::Level::Trace, module_path!()) {
+        let route = search.path().unwrap_or_default();
+        trace!(
+            "{}",
+            Renderer::new(&cg)
+                .path("shortest path", Color::Red, &route)
+                .render()
+        );
     }
 
-    match shortest_path(&cg, Position::at(0, 0), goal) {
+    match search.goal_distance() {
         Some(v) => Ok(v),
         None => Err(Error::new("no valid path to goal")),
     }
 }
 
 fn part2(dims: Dimensions, goal: Position, bytes: &Bytes) -> Result<Position> {
-    let mut cg = CharGrid::new(dims.w, dims.h);
-    cg.fill('.');
-
-    for pos in &bytes.positions {
-        cg.set_pos(*pos, '#');
-
-        if let None = shortest_path(&cg, Position::at(0, 0), goal) {
-            return Ok(*pos);
-        }
+    let reachable = |n: &usize| {
+        fallen(dims, &bytes.positions[..*n])
+            .distance_field(&[Position::at(0, 0)], |c| *c == '.')
+            .distance(goal)
+            .is_some()
+    };
+
+    // Once the goal is cut off more bytes can't open it up again, so the number of bytes which
+    // cuts it off can be binary searched for.
+    let counts: Vec<usize> = (0..=bytes.positions.len()).collect();
+    match counts.partition_point(reachable) {
+        0 => Err(Error::new("no valid path to goal")),
+        n if n == counts.len() => Err(Error::new("found path after consuming all bytes")),
+        n => Ok(bytes.positions[n - 1]),
     }
-
-    Err(Error::new("found path after consuming all bytes"))
 }
 
 #[test]
 fn test_part1() {
-    let bytes = Bytes::from_file("inputs/day18_example.txt").unwrap();
+    let bytes = Bytes::from_str(&read_example("inputs/day18_example.txt")).unwrap();
 
     assert_eq!(
         22,
@@ -121,7 +128,7 @@ fn test_part1() {
 
 #[test]
 fn test_part2() {
-    let bytes = Bytes::from_file("inputs/day18_example.txt").unwrap();
+    let bytes = Bytes::from_str(&read_example("inputs/day18_example.txt")).unwrap();
 
     assert_eq!(
         Position::at(6, 1),
@@ -1,19 +1,46 @@
 use crate::shared::*;
-use std::fs::read_to_string;
 
 pub struct Day19;
 
 impl Solution for Day19 {
-    fn part1(&self) -> Result<String> {
-        part1("inputs/day19.txt").map(|v| v.to_string())
+    type Input = Onsen;
+
+    fn parse(&self, input: &str) -> Result<Onsen> {
+        Onsen::from_str(input)
+    }
+
+    fn part1(&self, onsen: &Onsen) -> Result<Answer> {
+        Ok(part1(onsen).into())
     }
 
-    fn part2(&self) -> Result<String> {
-        part2("inputs/day19.txt").map(|v| v.to_string())
+    fn part2(&self, onsen: &Onsen) -> Result<Answer> {
+        Ok(part2(onsen).into())
     }
 }
 
 type Towels = Vec<String>;
+
+// The available towel patterns, and the designs we'd like to make from them.
+pub struct Onsen {
+    towels: Towels,
+    designs: Vec<String>,
+}
+
+impl Onsen {
+    fn from_str(input: &str) -> Result<Onsen> {
+        let docs = split_docs(input);
+        if docs.len() != 2 {
+            return Err(Error::new(
+                "expected a list of towels and a list of designs",
+            ));
+        }
+
+        Ok(Onsen {
+            towels: docs[0].split(", ").map(|v| v.trim().to_owned()).collect(),
+            designs: docs[1].lines().map(|v| v.to_owned()).collect(),
+        })
+    }
+}
 macro_rules! towels {
     ($($item:expr),* $(,)?) => {
         vec![$($item.to_string()),*]
@@ -37,48 +64,40 @@ fn possible_towel_paths(goal: &str, towels: &Towels) -> usize {
     arrivals[arrivals.len() - 1]
 }
 
-fn part1(path: &str) -> Result<usize> {
-    let contents = read_to_string(path)?;
-    let docs = split_docs(contents);
-    debug_assert!(docs.len() == 2);
-
-    let towels: Towels = docs[0].split(", ").map(|v| v.trim().to_owned()).collect();
-
+fn part1(onsen: &Onsen) -> usize {
     let mut count = 0;
 
-    for goal in docs[1].lines() {
-        if possible_towel_paths(goal, &towels) > 0 {
+    for goal in &onsen.designs {
+        if possible_towel_paths(goal, &onsen.towels) > 0 {
             count += 1;
         }
     }
 
-    Ok(count)
+    count
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(6, part1("inputs/day19_example.txt").unwrap());
-}
-
-fn part2(path: &str) -> Result<usize> {
-    let contents = read_to_string(path)?;
-    let docs = split_docs(contents);
-    debug_assert!(docs.len() == 2);
+    let onsen = Onsen::from_str(&read_example("inputs/day19_example.txt")).unwrap();
 
-    let towels: Towels = docs[0].split(", ").map(|v| v.trim().to_owned()).collect();
+    assert_eq!(6, part1(&onsen));
+}
 
+fn part2(onsen: &Onsen) -> usize {
     let mut count = 0;
 
-    for goal in docs[1].lines() {
-        count += possible_towel_paths(goal, &towels);
+    for goal in &onsen.designs {
+        count += possible_towel_paths(goal, &onsen.towels);
     }
 
-    Ok(count)
+    count
 }
 
 #[test]
 fn test_part2() {
-    assert_eq!(16, part2("inputs/day19_example.txt").unwrap());
+    let onsen = Onsen::from_str(&read_example("inputs/day19_example.txt")).unwrap();
+
+    assert_eq!(16, part2(&onsen));
 }
 
 #[test]
@@ -1,20 +1,20 @@
 use crate::shared::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 pub struct Day2;
 
 impl Solution for Day2 {
-    fn part1(&self) -> Result<String> {
-        let rl = ReportList::load("inputs/day2.txt")?;
+    type Input = ReportList;
 
-        Ok(rl.count_safe().to_string())
+    fn parse(&self, input: &str) -> Result<ReportList> {
+        ReportList::parse(input)
     }
 
-    fn part2(&self) -> Result<String> {
-        let rl = ReportList::load("inputs/day2.txt")?;
+    fn part1(&self, rl: &ReportList) -> Result<Answer> {
+        Ok(rl.count_safe().into())
+    }
 
-        Ok(rl.count_safe_with_problem_dampener().to_string())
+    fn part2(&self, rl: &ReportList) -> Result<Answer> {
+        Ok(rl.count_safe_with_problem_dampener().into())
     }
 }
 
@@ -60,28 +60,26 @@ fn assess_report_safety(report: &Report) -> Safety {
         result = Safety::Unsafe;
     }
 
-    println!(
+    trace!(
         "{:?} (deltas={:?}, n_pos={}) = {:?}",
-        report.ns, deltas, n_positive, result
+        report.ns,
+        deltas,
+        n_positive,
+        result
     );
 
     result
 }
 
-struct ReportList {
+pub struct ReportList {
     reports: Vec<Report>,
 }
 
 impl ReportList {
-    fn load(path: &str) -> Result<Self> {
+    fn parse(input: &str) -> Result<Self> {
         let mut reports: Vec<Report> = Vec::new();
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        for line in reader.lines() {
-            let line = line?;
-
+        for line in input.lines() {
             let mut ns: Vec<i32> = Vec::new();
             for str in line.split_whitespace() {
                 ns.push(str.parse::<i32>()?);
@@ -144,14 +142,16 @@ fn test_part_1_assess_report_safety() {
 
 #[test]
 fn test_part_1() {
-    let rl = ReportList::load("inputs/day2_example.txt").expect("failed to load reports");
+    let rl = ReportList::parse(&read_example("inputs/day2_example.txt"))
+        .expect("failed to load reports");
 
     assert_eq!(2, rl.count_safe());
 }
 
 #[test]
 fn test_part_2() {
-    let rl = ReportList::load("inputs/day2_example.txt").expect("failed to load reports");
+    let rl = ReportList::parse(&read_example("inputs/day2_example.txt"))
+        .expect("failed to load reports");
 
     assert_eq!(4, rl.count_safe_with_problem_dampener());
 }
@@ -3,56 +3,67 @@ use crate::shared::*;
 pub struct Day20;
 
 impl Solution for Day20 {
-    fn part1(&self) -> Result<String> {
-        part1("inputs/day20.txt", 100).map(|v| v.to_string())
-    }
+    type Input = CharGrid;
 
-    fn part2(&self) -> Result<String> {
-        todo!()
+    fn parse(&self, input: &str) -> Result<CharGrid> {
+        CharGrid::from_str(input)
     }
-}
-
-fn part1(path: &str, cheat_floor: usize) -> Result<usize> {
-    let mut mg = MetaGrid::<Option<usize>>::from_file(path)?;
 
-    let mut pos = mg.find_one('S')?;
-    let mut move_cost = 0;
-
-    let mut path = Vec::new();
+    fn part1(&self, cg: &CharGrid) -> Result<Answer> {
+        part1(cg, 100).map(Answer::from)
+    }
 
-    // Trace the 'correct' path.
-    loop {
-        mg.set_meta(pos, Some(move_cost));
-        path.push((pos, move_cost));
+    fn part2(&self, _cg: &CharGrid) -> Result<Answer> {
+        Err(Error::not_implemented())
+    }
+}
 
-        if mg.at(pos).0 == 'E' {
-            break;
-        }
+fn part1(cg: &CharGrid, cheat_floor: usize) -> Result<usize> {
+    let start = cg.find_one_pos('S')?;
+    let end = cg.find_one_pos('E')?;
 
-        let next = mg
-            .adjacent_orthogonal(pos)
-            .into_iter()
-            .filter(|(_, c, m)| (*c == '.' || *c == 'E') && m.is_none())
-            .collect::<Vec<_>>();
+    let open = |c: &char| *c != '#';
+    let from_start = cg.distance_field(&[start], open);
+    let to_end = cg.distance_field(&[end], open);
 
-        pos = next[0].0;
-        move_cost += 1;
+    let fastest = from_start
+        .distance(end)
+        .ok_or_else(|| Error::new("no path from start to end"))?;
+    let moves = to_end.layer();
+    trace!(
+        "{}",
+        Renderer::new(cg)
+            .heatmap("moves to end", |p| moves.get(p).copied().flatten())
+            .render()
+    );
+    if log::enabled(log::Level::Trace, module_path!()) {
+        // Which half of the track each cell is in, by whichever end is closer.
+        let ends = cg.distance_field(&[start, end], open);
+        let nearer_end = ends
+            .reachable()
+            .filter(|(p, _)| ends.closest_source(*p) == Some(end))
+            .map(|(p, _)| p);
+        trace!(
+            "{}",
+            Renderer::new(cg)
+                .highlight("nearer the end", Color::Cyan, nearer_end)
+                .render()
+        );
     }
 
     let mut found_cheats = 0;
 
-    for (p, cost) in path {
+    for (p, cost) in from_start.reachable() {
         // assumption: only straight lines are viable cheats
-        for dir in [UP, DOWN, LEFT, RIGHT] {
+        for dir in ORTHOGONAL.iter().copied() {
             let cheat_start = p + dir;
             let cheat_end = p + dir + dir;
 
-            if let ('#', _) = mg.at(cheat_start) {
-                if mg.in_bounds(cheat_end) {
-                    if let (_, Some(other_cost)) = mg.at(cheat_end) {
-                        if *other_cost > (cost + 2) && *other_cost - (cost + 2) >= cheat_floor {
-                            found_cheats += 1;
-                        }
+            if cg.get(cheat_start) == Some(&'#') {
+                if let Some(remaining) = to_end.distance(cheat_end) {
+                    let time = cost + 2 + remaining;
+                    if time < fastest && fastest - time >= cheat_floor {
+                        found_cheats += 1;
                     }
                 }
             }
@@ -64,6 +75,8 @@ fn part1(path: &str, cheat_floor: usize) -> Result<usize> {
 
 #[test]
 fn test_part1() {
-    assert_eq!(5, part1("inputs/day20_example.txt", 20).unwrap());
-    assert_eq!(44, part1("inputs/day20_example.txt", 1).unwrap());
+    let cg = CharGrid::from_str(&read_example("inputs/day20_example.txt")).unwrap();
+
+    assert_eq!(5, part1(&cg, 20).unwrap());
+    assert_eq!(44, part1(&cg, 1).unwrap());
 }
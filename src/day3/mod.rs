@@ -1,38 +1,41 @@
 use crate::shared::*;
-use std::fs::read_to_string;
 
 pub struct Day3;
 
 impl Solution for Day3 {
-    fn part1(&self) -> Result<String> {
-        compute("inputs/day3.txt").map(|v| v.to_string())
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(&self, input: &String) -> Result<Answer> {
+        compute(input).map(Answer::from)
     }
 
-    fn part2(&self) -> Result<String> {
-        compute_part2("inputs/day3.txt").map(|v| v.to_string())
+    fn part2(&self, input: &String) -> Result<Answer> {
+        compute_part2(input).map(Answer::from)
     }
 }
 
-fn compute(path: &str) -> Result<i32> {
+fn compute(input: &str) -> Result<i32> {
     let r = regex::Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    let content = read_to_string(path)?;
 
     let mut sum = 0;
-    for (_, [lh, rh]) in r.captures_iter(&content).map(|c| c.extract()) {
+    for (_, [lh, rh]) in r.captures_iter(input).map(|c| c.extract()) {
         sum += lh.parse::<i32>()? * rh.parse::<i32>()?;
     }
 
     Ok(sum)
 }
 
-fn compute_part2(path: &str) -> Result<i32> {
+fn compute_part2(input: &str) -> Result<i32> {
     let r = regex::Regex::new(r"(mul|do|don't)\(((\d{1,3}),(\d{1,3}))?\)").unwrap();
-    let content = read_to_string(path)?;
 
     let mut sum = 0;
     let mut enabled = true;
-    for captures in r.captures_iter(&content) {
-        println!("captures={:?}", captures);
+    for captures in r.captures_iter(input) {
+        trace!("captures={:?}", captures);
         match captures.get(1).unwrap().as_str() {
             "mul" if enabled => {
                 let lh = captures.get(3).unwrap().as_str();
@@ -54,7 +57,7 @@ fn compute_part2(path: &str) -> Result<i32> {
 fn test_part1() {
     assert_eq!(
         161,
-        compute("inputs/day3_example.txt").expect("compute failed")
+        compute(&read_example("inputs/day3_example.txt")).expect("compute failed")
     );
 }
 
@@ -62,6 +65,6 @@ fn test_part1() {
 fn test_part2() {
     assert_eq!(
         48,
-        compute_part2("inputs/day3_example2.txt").expect("compute failed")
+        compute_part2(&read_example("inputs/day3_example2.txt")).expect("compute failed")
     )
 }
@@ -1,17 +1,21 @@
 use crate::shared::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::num::ParseIntError;
 
 pub struct Day5;
 
 impl Solution for Day5 {
-    fn part1(&self) -> Result<String> {
-        score_part1("inputs/day5.txt").map(|v| v.to_string())
+    type Input = (Rules, Vec<Update>);
+
+    fn parse(&self, input: &str) -> Result<(Rules, Vec<Update>)> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (rules, updates): &(Rules, Vec<Update>)) -> Result<Answer> {
+        Ok(score_part1(rules, updates).into())
     }
 
-    fn part2(&self) -> Result<String> {
-        score_part2("inputs/day5.txt").map(|v| v.to_string())
+    fn part2(&self, (rules, updates): &(Rules, Vec<Update>)) -> Result<Answer> {
+        Ok(score_part2(rules, updates).into())
     }
 }
 
@@ -35,12 +39,12 @@ impl Rule {
     }
 }
 
-struct Rules {
+pub struct Rules {
     rules: Vec<Rule>,
 }
 
-#[derive(Debug)]
-struct Update {
+#[derive(Debug, Clone)]
+pub struct Update {
     ns: Vec<u8>,
 
     // An occurrence map of the position of each page number in the list, used to validate rules.
@@ -134,10 +138,7 @@ impl Update {
     }
 }
 
-fn parse_file(path: &str) -> Result<(Rules, Vec<Update>)> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-
+fn parse_input(input: &str) -> Result<(Rules, Vec<Update>)> {
     let mut rules = Rules { rules: Vec::new() };
     let mut updates = Vec::new();
 
@@ -146,61 +147,58 @@ fn parse_file(path: &str) -> Result<(Rules, Vec<Update>)> {
         Updates,
     }
     let mut mode = Mode::Rules;
-    for line in reader.lines() {
-        let line = line?;
-
+    for line in input.lines() {
         if line.trim() == "" {
             mode = Mode::Updates;
             continue;
         }
 
         match mode {
-            Mode::Rules => rules.rules.push(Rule::from_str(&line)?),
-            Mode::Updates => updates.push(Update::from_str(&line)?),
+            Mode::Rules => rules.rules.push(Rule::from_str(line)?),
+            Mode::Updates => updates.push(Update::from_str(line)?),
         }
     }
 
     Ok((rules, updates))
 }
 
-fn score_part1(path: &str) -> Result<usize> {
-    let (rules, updates) = parse_file(path)?;
-
+fn score_part1(rules: &Rules, updates: &[Update]) -> usize {
     let mut sum: usize = 0;
     for update in updates {
-        if update.check_rules(&rules).is_ok() {
+        if update.check_rules(rules).is_ok() {
             sum += update.midpoint() as usize;
         }
     }
 
-    Ok(sum)
+    sum
 }
 
-fn score_part2(path: &str) -> Result<usize> {
-    let (rules, updates) = parse_file(path)?;
-
+fn score_part2(rules: &Rules, updates: &[Update]) -> usize {
     let mut failed_updates: Vec<_> = updates
-        .into_iter()
-        .filter(|u| u.check_rules(&rules).is_err())
+        .iter()
+        .filter(|u| u.check_rules(rules).is_err())
+        .cloned()
         .collect();
 
     let mut sum: usize = 0;
     for update in &mut failed_updates {
-        update.correct_for(&rules);
+        update.correct_for(rules);
         sum += update.midpoint() as usize;
     }
 
-    Ok(sum)
+    sum
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(143, score_part1("inputs/day5_example.txt").unwrap());
+    let (rules, updates) = parse_input(&read_example("inputs/day5_example.txt")).unwrap();
+
+    assert_eq!(143, score_part1(&rules, &updates));
 }
 
 #[test]
 fn test_correct_update() {
-    let (rules, _) = parse_file("inputs/day5_example.txt").unwrap();
+    let (rules, _) = parse_input(&read_example("inputs/day5_example.txt")).unwrap();
 
     let mut update = Update::from_str("75,97,47,61,53").unwrap();
     update.correct_for(&rules);
@@ -210,5 +208,7 @@ fn test_correct_update() {
 
 #[test]
 fn test_part2() {
-    assert_eq!(123, score_part2("inputs/day5_example.txt").unwrap());
+    let (rules, updates) = parse_input(&read_example("inputs/day5_example.txt")).unwrap();
+
+    assert_eq!(123, score_part2(&rules, &updates));
 }
@@ -3,17 +3,23 @@ use crate::shared::*;
 pub struct Day6;
 
 impl Solution for Day6 {
-    fn part1(&self) -> Result<String> {
-        part1_walk("inputs/day6.txt").map(|(v, _)| v.to_string())
+    type Input = CharGrid;
+
+    fn parse(&self, input: &str) -> Result<CharGrid> {
+        CharGrid::from_str(input)
+    }
+
+    fn part1(&self, cg: &CharGrid) -> Result<Answer> {
+        part1_walk(cg).map(|(v, _)| v.into())
     }
 
-    fn part2(&self) -> Result<String> {
-        part2("inputs/day6.txt").map(|v| v.to_string())
+    fn part2(&self, cg: &CharGrid) -> Result<Answer> {
+        part2(cg).map(Answer::from)
     }
 }
 
-fn part1_walk(path: &str) -> Result<(usize, CharGrid)> {
-    let mut cg = CharGrid::from_file(path)?;
+fn part1_walk(original: &CharGrid) -> Result<(usize, CharGrid)> {
+    let mut cg = original.clone();
 
     let (x, y) = cg.find_one('^')?;
     let mut pos = Position { x, y };
@@ -35,10 +41,9 @@ fn part1_walk(path: &str) -> Result<(usize, CharGrid)> {
     }
 }
 
-fn part2(path: &str) -> Result<usize> {
-    let original = CharGrid::from_file(path)?;
+fn part2(original: &CharGrid) -> Result<usize> {
     let (start_x, start_y) = original.find_one('^')?;
-    let (_, walked) = part1_walk(path)?;
+    let (_, walked) = part1_walk(original)?;
 
     let mut ct = 0;
     for pos in walked.find_all_pos('X') {
@@ -49,7 +54,7 @@ fn part2(path: &str) -> Result<usize> {
         let mut hypothetical = original.clone();
         hypothetical.set(pos.x, pos.y, '#');
 
-        if WalkResult::InfiniteLoop == part2_walk(&mut hypothetical)? {
+        if guard_loops(&hypothetical, Position::at(start_x, start_y)) {
             ct += 1;
         }
     }
@@ -57,57 +62,33 @@ fn part2(path: &str) -> Result<usize> {
     Ok(ct)
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum WalkResult {
-    ExitMap,
-    InfiniteLoop,
-}
-fn part2_walk(cg: &mut CharGrid) -> Result<WalkResult> {
-    let (x, y) = cg.find_one('^')?;
-    let mut pos = Position { x, y };
-    let mut direction = UP;
+// The guard loops if their position and heading ever repeat, rather than walking off the map.
+// Only the turns matter, so each step walks straight up to the next obstruction.
+fn guard_loops(cg: &CharGrid, start: Position) -> bool {
+    let step = |(pos, direction): &(Position, Direction)| {
+        let obstruction = cg.first_blocked(*pos, pos + *direction, |c| *c == '#')?;
 
-    loop {
-        let next = pos.add(direction);
+        // Turning twice is facing back the way they came, to the cell before the obstruction.
+        Some((
+            obstruction + direction.clockwise().clockwise(),
+            direction.clockwise(),
+        ))
+    };
 
-        if !cg.in_bounds(next) {
-            return Ok(WalkResult::ExitMap);
-        }
-
-        match cg.at(next.x, next.y) {
-            '#' => direction = direction.clockwise(),
-            '.' | '1'..'6' => {
-                match cg.at(pos.x, pos.y) {
-                    '^' | '.' => cg.set(pos.x, pos.y, '1'),
-                    '1' => cg.set(pos.x, pos.y, '2'),
-                    '2' => cg.set(pos.x, pos.y, '3'),
-                    '3' => cg.set(pos.x, pos.y, '4'),
-                    '4' => cg.set(pos.x, pos.y, '5'),
-                    '5' => cg.set(pos.x, pos.y, '6'),
-                    c => panic!(
-                        "unexpected char at position(x={}, y={}): '{}'",
-                        pos.x, pos.y, c
-                    ),
-                }
-
-                pos = next
-            }
-            '6' => return Ok(WalkResult::InfiniteLoop),
-            c => return Err(Error::new(&format!("unexpected char: '{}'", c))),
-        }
-    }
+    brent((start, UP), step).is_some()
 }
 
 #[test]
 fn test_part1_example() {
-    let (ct, _) = part1_walk("inputs/day6_example.txt").unwrap();
+    let (ct, _) =
+        part1_walk(&CharGrid::from_str(&read_example("inputs/day6_example.txt")).unwrap()).unwrap();
 
     assert_eq!(41, ct);
 }
 
 #[test]
 fn test_part2_example() {
-    let ct = part2("inputs/day6_example.txt").unwrap();
+    let ct = part2(&CharGrid::from_str(&read_example("inputs/day6_example.txt")).unwrap()).unwrap();
 
     assert_eq!(6, ct);
 }
@@ -1,16 +1,21 @@
 use crate::shared::*;
-use std::fs::read_to_string;
 use std::num::ParseIntError;
 
 pub struct Day7;
 
 impl Solution for Day7 {
-    fn part1(&self) -> Result<String> {
-        part1("inputs/day7.txt", &[Op::Add, Op::Mul]).map(|v| v.to_string())
+    type Input = Vec<Equation>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Equation>> {
+        parse_equations(input)
+    }
+
+    fn part1(&self, equations: &Vec<Equation>) -> Result<Answer> {
+        Ok(part1(equations, &[Op::Add, Op::Mul]).into())
     }
 
-    fn part2(&self) -> Result<String> {
-        part1("inputs/day7.txt", &[Op::Add, Op::Mul, Op::Concat]).map(|v| v.to_string())
+    fn part2(&self, equations: &Vec<Equation>) -> Result<Answer> {
+        Ok(part1(equations, &[Op::Add, Op::Mul, Op::Concat]).into())
     }
 }
 
@@ -89,11 +94,14 @@ fn op_permutations(n: usize, ops: &[Op]) -> Vec<Vec<Op>> {
     }
 }
 
-fn part1(path: &str, ops: &[Op]) -> Result<u64> {
-    let contents = read_to_string(path)?;
+pub struct Equation {
+    solution: u64,
+    terms: Vec<u64>,
+}
 
-    let mut sum = 0;
-    for line in contents.lines() {
+fn parse_equations(input: &str) -> Result<Vec<Equation>> {
+    let mut equations = Vec::new();
+    for line in input.lines() {
         let parts = line.split(": ").collect::<Vec<_>>();
         if parts.len() != 2 {
             return Err(Error::new("unexpected number of parts in input line"));
@@ -105,26 +113,33 @@ fn part1(path: &str, ops: &[Op]) -> Result<u64> {
             .map(|v| v.parse::<u64>())
             .collect::<std::result::Result<Vec<_>, ParseIntError>>()?;
 
-        if is_solvable(solution, &terms, ops) {
-            sum += solution;
+        equations.push(Equation { solution, terms });
+    }
+
+    Ok(equations)
+}
+
+fn part1(equations: &[Equation], ops: &[Op]) -> u64 {
+    let mut sum = 0;
+    for eq in equations {
+        if is_solvable(eq.solution, &eq.terms, ops) {
+            sum += eq.solution;
         }
     }
 
-    Ok(sum)
+    sum
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(
-        3749,
-        part1("inputs/day7_example.txt", &[Op::Add, Op::Mul]).unwrap()
-    );
+    let equations = parse_equations(&read_example("inputs/day7_example.txt")).unwrap();
+
+    assert_eq!(3749, part1(&equations, &[Op::Add, Op::Mul]));
 }
 
 #[test]
 fn test_part2() {
-    assert_eq!(
-        11387,
-        part1("inputs/day7_example.txt", &[Op::Add, Op::Mul, Op::Concat]).unwrap()
-    );
+    let equations = parse_equations(&read_example("inputs/day7_example.txt")).unwrap();
+
+    assert_eq!(11387, part1(&equations, &[Op::Add, Op::Mul, Op::Concat]));
 }
@@ -3,17 +3,22 @@ use crate::shared::*;
 pub struct Day8;
 
 impl Solution for Day8 {
-    fn part1(&self) -> Result<String> {
-        count_antinodes("inputs/day8.txt").map(|v| v.to_string())
+    type Input = CharGrid;
+
+    fn parse(&self, input: &str) -> Result<CharGrid> {
+        CharGrid::from_str(input)
+    }
+
+    fn part1(&self, map: &CharGrid) -> Result<Answer> {
+        Ok(count_antinodes(map).into())
     }
 
-    fn part2(&self) -> Result<String> {
-        count_antinodes_p2("inputs/day8.txt").map(|v| v.to_string())
+    fn part2(&self, map: &CharGrid) -> Result<Answer> {
+        Ok(count_antinodes_p2(map).into())
     }
 }
 
-fn count_antinodes(path: &str) -> Result<usize> {
-    let map = CharGrid::from_file(path)?;
+fn count_antinodes(map: &CharGrid) -> usize {
     let mut antinodes = map.clone();
 
     for antenna in map.uniq_chars() {
@@ -23,32 +28,30 @@ fn count_antinodes(path: &str) -> Result<usize> {
 
         let locations = map.find_all_pos(antenna);
         if locations.len() == 1 {
-            println!("found only one antenna of type '{}'", antenna);
+            debug!("found only one antenna of type '{}'", antenna);
             continue;
         }
 
-        let mut mark_antinode = |p: Position| {
-            if antinodes.in_bounds(p) {
-                antinodes.set_pos(p, '#');
-            }
-        };
+        let bounds = (Position::at(0, 0), Dimensions::of(map.width(), map.height()));
 
         for positions in combinations(2, &locations) {
-            let p1 = positions[0];
-            let p2 = positions[1];
-
-            let offset = p1.delta(p2);
-
-            mark_antinode(p1.add_pos(offset));
-            mark_antinode(p2.sub_pos(offset));
+            let (p1, p2) = (positions[0], positions[1]);
+
+            // The first point past the other antenna, as far again from it.
+            let antinodes_of_pair = [
+                p1.ray(p2, Stepping::Raw).within(bounds).nth(2),
+                p2.ray(p1, Stepping::Raw).within(bounds).nth(2),
+            ];
+            for p in antinodes_of_pair.into_iter().flatten() {
+                antinodes.set_pos(p, '#');
+            }
         }
     }
 
-    Ok(antinodes.count('#'))
+    antinodes.count('#')
 }
 
-fn count_antinodes_p2(path: &str) -> Result<usize> {
-    let map = CharGrid::from_file(path)?;
+fn count_antinodes_p2(map: &CharGrid) -> usize {
     let mut antinodes = map.clone();
 
     for antenna in map.uniq_chars() {
@@ -58,37 +61,39 @@ fn count_antinodes_p2(path: &str) -> Result<usize> {
 
         let locations = map.find_all_pos(antenna);
         if locations.len() == 1 {
-            println!("found only one antenna of type '{}'", antenna);
+            debug!("found only one antenna of type '{}'", antenna);
             continue;
         }
 
-        for positions in combinations(2, &locations) {
-            let offset = positions[0].delta(positions[1]);
+        let bounds = (Position::at(0, 0), Dimensions::of(map.width(), map.height()));
 
-            antinodes.set_pos(positions[0], '#');
-            let mut target = positions[0].sub_pos(offset);
-            while antinodes.in_bounds(target) {
-                antinodes.set_pos(target, '#');
-                target = target.sub_pos(offset);
-            }
+        // Every lattice point in line with the pair, both ways from either antenna.
+        for positions in combinations(2, &locations) {
+            let (p1, p2) = (positions[0], positions[1]);
 
-            let mut target = positions[0].add_pos(offset);
-            while antinodes.in_bounds(target) {
-                antinodes.set_pos(target, '#');
-                target = target.add_pos(offset);
+            let line = p1
+                .ray(p2, Stepping::Reduced)
+                .within(bounds)
+                .chain(p2.ray(p1, Stepping::Reduced).within(bounds));
+            for p in line {
+                antinodes.set_pos(p, '#');
             }
         }
     }
 
-    Ok(antinodes.count('#'))
+    antinodes.count('#')
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(14, count_antinodes("inputs/day8_example.txt").unwrap());
+    let map = CharGrid::from_str(&read_example("inputs/day8_example.txt")).unwrap();
+
+    assert_eq!(14, count_antinodes(&map));
 }
 
 #[test]
 fn test_part2() {
-    assert_eq!(34, count_antinodes_p2("inputs/day8_example.txt").unwrap());
+    let map = CharGrid::from_str(&read_example("inputs/day8_example.txt")).unwrap();
+
+    assert_eq!(34, count_antinodes_p2(&map));
 }
@@ -1,42 +1,43 @@
 use crate::shared::*;
-use std::fs::read_to_string;
 
 pub struct Day9;
 
 impl Solution for Day9 {
-    fn part1(&self) -> Result<String> {
-        let mut disk = Disk::from_file("inputs/day9.txt")?;
+    type Input = Disk;
+
+    fn parse(&self, input: &str) -> Result<Disk> {
+        Disk::from_map(input)
+    }
+
+    fn part1(&self, disk: &Disk) -> Result<Answer> {
+        let mut disk = disk.clone();
         disk.fragment();
 
-        Ok(disk.checksum().to_string())
+        Ok(disk.checksum().into())
     }
 
-    fn part2(&self) -> Result<String> {
-        let mut disk = Disk::from_file("inputs/day9.txt")?;
+    fn part2(&self, disk: &Disk) -> Result<Answer> {
+        let mut disk = disk.clone();
         disk.compactify();
 
-        Ok(disk.checksum().to_string())
+        Ok(disk.checksum().into())
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct FileID(u32);
 
-#[derive(Debug)]
-struct Disk {
+#[derive(Debug, Clone)]
+pub struct Disk {
     blocks: Vec<Option<FileID>>,
 }
 
 impl Disk {
-    fn from_file(path: &str) -> Result<Disk> {
-        Disk::from_map(&read_to_string(path)?)
-    }
-
     fn from_map(map: &str) -> Result<Disk> {
         let mut blocks = Vec::new();
 
         let mut file_id = 0;
-        for (idx, c) in map.chars().enumerate() {
+        for (idx, c) in map.trim_end().chars().enumerate() {
             let len = match c.to_digit(10) {
                 Some(d) => d,
                 None => return Err(Error::new("encountered non-digit in Disk::from_map")),
@@ -147,8 +148,12 @@ impl Disk {
                 }
 
                 Some((idx, Gap { start, len })) => {
-                    #[cfg(test)]
-                    let before = disk_string(&self);
+                    trace!(
+                        "moving {} blocks from {} to {}",
+                        move_len,
+                        move_start,
+                        start
+                    );
 
                     for i in 0..move_len {
                         self.blocks[start + i] = self.blocks[move_start + i];
@@ -159,9 +164,6 @@ impl Disk {
                         start: start + move_len,
                         len: len - move_len, // this may be zero, that's ok.
                     };
-
-                    #[cfg(test)]
-                    println!("{} -> {}", before, disk_string(&self));
                 }
             }
         }
@@ -189,7 +191,7 @@ fn test_from_map() {
 
 #[test]
 fn test_part1() {
-    let mut disk = Disk::from_file("inputs/day9_example.txt").unwrap();
+    let mut disk = Disk::from_map(&read_example("inputs/day9_example.txt")).unwrap();
     assert_eq!(
         "00...111...2...333.44.5555.6666.777.888899",
         disk_string(&disk)
@@ -206,7 +208,7 @@ fn test_part1() {
 
 #[test]
 fn test_part2() {
-    let mut disk = Disk::from_file("inputs/day9_example.txt").unwrap();
+    let mut disk = Disk::from_map(&read_example("inputs/day9_example.txt")).unwrap();
 
     assert_eq!(
         "00...111...2...333.44.5555.6666.777.888899",
@@ -1,75 +1,259 @@
+mod runner;
 mod shared;
 
+use runner::answers::{Answers, Check};
+use runner::args::{Args, Format, Parts, Target};
+use runner::bench;
+use runner::registry::{registry, Registry};
+use runner::InputSource;
 use shared::*;
-use std::collections::HashMap;
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use std::path::Path;
+
+registry! {
+    1 => day1::Day1 {
+        title: "Historian Hysteria",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["lists"],
+    },
+    2 => day2::Day2 {
+        title: "Red-Nosed Reports",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["lists"],
+    },
+    3 => day3::Day3 {
+        title: "Mull It Over",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["parsing", "regex"],
+    },
+    4 => day4::Day4 {
+        title: "Ceres Search",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "word-search"],
+    },
+    5 => day5::Day5 {
+        title: "Print Queue",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["ordering"],
+    },
+    6 => day6::Day6 {
+        title: "Guard Gallivant",
+        runtime: Slow,
+        parts: [1, 2],
+        tags: ["grid", "simulation", "cycles"],
+    },
+    7 => day7::Day7 {
+        title: "Bridge Repair",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["combinatorics"],
+    },
+    8 => day8::Day8 {
+        title: "Resonant Collinearity",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "geometry"],
+    },
+    9 => day9::Day9 {
+        title: "Disk Fragmenter",
+        runtime: Slow,
+        parts: [1, 2],
+        tags: ["simulation"],
+    },
+    10 => day10::Day10 {
+        title: "Hoof It",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "pathfinding"],
+    },
+    11 => day11::Day11 {
+        title: "Plutonian Pebbles",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["memoization"],
+    },
+    12 => day12::Day12 {
+        title: "Garden Groups",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "regions"],
+    },
+    13 => day13::Day13 {
+        title: "Claw Contraption",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["math"],
+    },
+    14 => day14::Day14 {
+        title: "Restroom Redoubt",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "simulation"],
+    },
+    15 => day15::Day15 {
+        title: "Warehouse Woes",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "simulation"],
+    },
+    16 => day16::Day16 {
+        title: "Reindeer Maze",
+        runtime: Slow,
+        parts: [1, 2],
+        tags: ["grid", "pathfinding"],
+    },
+    // Part 2 is an interactive explorer rather than a solution, it can still be run on its own.
+    17 => day17::Day17 {
+        title: "Chronospatial Computer",
+        runtime: Fast,
+        parts: [1],
+        tags: ["vm", "interactive"],
+    },
+    18 => day18::Day18 {
+        title: "RAM Run",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["grid", "pathfinding"],
+    },
+    19 => day19::Day19 {
+        title: "Linen Layout",
+        runtime: Fast,
+        parts: [1, 2],
+        tags: ["strings", "memoization"],
+    },
+    20 => day20::Day20 {
+        title: "Race Condition",
+        runtime: Fast,
+        parts: [1],
+        tags: ["grid", "pathfinding"],
+    },
+}
 
 fn main() -> Result<()> {
-    let args: Vec<_> = std::env::args().collect();
-    if args.len() != 3 {
-        return Err(Error::new("usage: aoc2024 <day#> <part#>"));
+    let argv: Vec<_> = std::env::args().skip(1).collect();
+    let args = Args::parse(&argv)?;
+    shared::log::init(args.log.clone());
+
+    let registry = registry();
+
+    if let (Target::Day(day, Parts::One(part)), Some(path)) = (&args.target, &args.frames) {
+        return save_frames(&registry, *day, *part, &args.input, path);
+    }
+
+    let mut outcomes = match &args.target {
+        Target::All => {
+            let selected = registry.select(&args.filter);
+            runner::run_all(&registry, &selected, &[1, 2], &args.input)
+        }
+        Target::Day(day, Parts::Both) => {
+            registry.get(*day)?;
+            runner::run_all(&registry, &[*day], &[1, 2], &args.input)
+        }
+        Target::Day(day, Parts::One(part)) => {
+            vec![runner::run_one(registry.get(*day)?, *part, &args.input)]
+        }
+        Target::Bench(day, part) => return run_bench(&registry, *day, *part, &args),
+        Target::List => {
+            print!("{}", registry.draw());
+            return Ok(());
+        }
+        Target::New(day, title) => {
+            if registry.get(*day).is_ok() {
+                return Err(Error::new(&format!("day {} already exists", day)));
+            }
+
+            for path in runner::scaffold::new_day(Path::new("."), *day, title)? {
+                println!("wrote {}", path);
+            }
+            return Ok(());
+        }
+    };
+
+    // Known answers are for the real puzzle inputs, so anything else is left unknown.
+    let mut answers = Answers::load(&args.answers)?;
+    if args.input == InputSource::Default {
+        answers.verify(&mut outcomes);
+    }
+
+    match (args.format, &args.target, outcomes.as_slice()) {
+        (Format::Json, _, _) => {
+            for outcome in &outcomes {
+                println!(
+                    "{}",
+                    runner::json::outcome_line(outcome, &args.input.path(outcome.day))
+                );
+            }
+        }
+        (Format::Table, Target::Day(_, Parts::One(_)), [outcome]) => {
+            match (&outcome.result, &outcome.check) {
+                (Ok(v), Check::Unknown) => println!("result: {}", v),
+                (Ok(v), _) => println!("result: {} ({})", v, outcome.status()),
+                (Err(_), _) => println!("{}", outcome.status()),
+            }
+        }
+        _ => print!("{}", runner::draw_table(&outcomes)),
     }
 
-    let day = args[1].parse::<u32>()?;
-    let part = args[2].parse::<u32>()?;
-
-    let mut days: HashMap<u32, Box<dyn Solution>> = HashMap::new();
-    days.insert(1, Box::new(day1::Day1));
-    days.insert(2, Box::new(day2::Day2));
-    days.insert(3, Box::new(day3::Day3));
-    days.insert(4, Box::new(day4::Day4));
-    days.insert(5, Box::new(day5::Day5));
-    days.insert(6, Box::new(day6::Day6));
-    days.insert(7, Box::new(day7::Day7));
-    days.insert(8, Box::new(day8::Day8));
-    days.insert(9, Box::new(day9::Day9));
-    days.insert(10, Box::new(day10::Day10));
-    days.insert(11, Box::new(day11::Day11));
-    days.insert(12, Box::new(day12::Day12));
-    days.insert(13, Box::new(day13::Day13));
-    days.insert(14, Box::new(day14::Day14));
-    days.insert(15, Box::new(day15::Day15));
-    days.insert(16, Box::new(day16::Day16));
-    days.insert(17, Box::new(day17::Day17));
-    days.insert(18, Box::new(day18::Day18));
-    days.insert(19, Box::new(day19::Day19));
-    days.insert(20, Box::new(day20::Day20));
-
-    if let Some(solution) = days.get(&day) {
-        let result = if part == 1 {
-            solution.part1()
-        } else if part == 2 {
-            solution.part2()
-        } else {
-            return Err(Error::new("invalid part number"));
-        };
-
-        match result {
-            Ok(v) => println!("result: {}", v),
-            Err(e) => println!("error: {}", e),
+    if args.record && args.input == InputSource::Default {
+        let added = answers.record(&outcomes);
+        if added > 0 {
+            answers.save(&args.answers)?;
         }
-    } else {
-        return Err(Error::new(&format!("day {day} not found")));
+        eprintln!("recorded {} new answer(s) in {}", added, args.answers);
+    }
+
+    let failed = outcomes.iter().filter(|o| o.is_failed()).count();
+    let wrong = outcomes.iter().filter(|o| o.is_wrong()).count();
+    if failed > 0 || wrong > 0 {
+        return Err(Error::new(&format!(
+            "{} of {} parts failed, {} wrong",
+            failed,
+            outcomes.len(),
+            wrong
+        )));
+    }
+
+    Ok(())
+}
+
+// Saves the part's frames instead of solving it.
+fn save_frames(
+    registry: &Registry,
+    day: u32,
+    part: u32,
+    source: &InputSource,
+    path: &str,
+) -> Result<()> {
+    let solution = &registry.get(day)?.solution;
+
+    let input = source.read(day)?;
+    let frames = runner::panics::catch(|| {
+        solution
+            .parse_any(&input)
+            .and_then(|parsed| solution.frames_any(part, parsed.as_ref()))
+    })?;
+
+    let written = frames.save(Path::new(path))?;
+    eprintln!("wrote {} frame(s) to {}", written, path);
+
+    Ok(())
+}
+
+fn run_bench(registry: &Registry, day: u32, part: u32, args: &Args) -> Result<()> {
+    let solution = &registry.get(day)?.solution;
+
+    let input = args.input.read(day)?;
+    let result =
+        runner::panics::catch(|| bench::bench(day, part, solution.as_ref(), &input, &args.bench))?;
+    print!("{}", bench::draw(&result));
+
+    if let Some(path) = &args.bench.csv {
+        let label = args.bench.label.clone().unwrap_or_else(bench::git_label);
+        bench::append_csv(path, &label, &result)?;
     }
 
     Ok(())
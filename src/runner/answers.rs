@@ -0,0 +1,180 @@
+use crate::runner::Outcome;
+use crate::shared::*;
+use std::collections::BTreeMap;
+
+pub const DEFAULT_PATH: &str = "inputs/answers.txt";
+
+// How a part's answer compares against the known answers file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+// Known puzzle answers keyed by (day, part). The file format is one answer per line, as
+// "<day> <part> <answer>", with blank lines and lines starting with '#' ignored. The answer is
+// the remainder of the line so it may contain spaces or commas.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    known: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    // Loads the answers at path, treating a missing file as having no known answers.
+    pub fn load(path: &str) -> Result<Answers> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::from_str(&contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn from_str(str: &str) -> Result<Answers> {
+        let mut known = BTreeMap::new();
+
+        for (i, line) in str.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, char::is_whitespace);
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(day), Some(part), Some(answer)) if !answer.trim().is_empty() => {
+                    known.insert(
+                        (day.parse::<u32>()?, part.parse::<u32>()?),
+                        answer.trim().to_owned(),
+                    );
+                }
+                _ => {
+                    return Err(Error::new(&format!(
+                        "expected '<day> <part> <answer>' on line {} of answers",
+                        i + 1
+                    )))
+                }
+            }
+        }
+
+        Ok(Answers { known })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.known.get(&(day, part)).map(|v| v.as_str())
+    }
+
+    // Compares the canonical text form of the answer against the known answer.
+    pub fn check(&self, day: u32, part: u32, answer: &Answer) -> Check {
+        match self.get(day, part) {
+            None => Check::Unknown,
+            Some(expected) if expected == answer.to_string() => Check::Correct,
+            Some(expected) => Check::Wrong {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+
+    // Marks each successful outcome as correct, wrong or unknown.
+    pub fn verify(&self, outcomes: &mut [Outcome]) {
+        for outcome in outcomes {
+            outcome.check = match &outcome.result {
+                Ok(answer) => self.check(outcome.day, outcome.part, answer),
+                Err(_) => Check::Unknown,
+            };
+        }
+    }
+
+    // Adds the answers of successful outcomes which have no known answer yet, returning how many
+    // were added. Wrong answers are never overwritten, those have to be fixed by hand.
+    pub fn record(&mut self, outcomes: &[Outcome]) -> usize {
+        let mut added = 0;
+
+        for outcome in outcomes {
+            if let (Ok(answer), Check::Unknown) = (&outcome.result, &outcome.check) {
+                self.known
+                    .insert((outcome.day, outcome.part), answer.to_string());
+                added += 1;
+            }
+        }
+
+        added
+    }
+
+    pub fn draw(&self) -> String {
+        let mut str = String::new();
+
+        for ((day, part), answer) in &self.known {
+            str.push_str(&format!("{} {} {}\n", day, part, answer));
+        }
+
+        str
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        Ok(std::fs::write(path, self.draw())?)
+    }
+}
+
+#[test]
+fn test_from_str() {
+    let answers = Answers::from_str(
+        "# day part answer\n\
+         1 1 11\n\
+         \n\
+         17 1 4,6,3,5\n\
+         18 2   6,1  \n",
+    )
+    .unwrap();
+
+    assert_eq!(Some("11"), answers.get(1, 1));
+    assert_eq!(Some("4,6,3,5"), answers.get(17, 1));
+    assert_eq!(Some("6,1"), answers.get(18, 2));
+    assert_eq!(None, answers.get(1, 2));
+
+    assert!(Answers::from_str("1 1").is_err());
+    assert!(Answers::from_str("one 1 11").is_err());
+}
+
+#[test]
+fn test_check() {
+    let answers = Answers::from_str("1 1 11\n").unwrap();
+
+    assert_eq!(Check::Correct, answers.check(1, 1, &Answer::Int(11)));
+    assert_eq!(
+        Check::Wrong {
+            expected: "11".to_owned()
+        },
+        answers.check(1, 1, &Answer::Int(12))
+    );
+    assert_eq!(Check::Unknown, answers.check(1, 2, &Answer::Int(12)));
+
+    let answers = Answers::from_str("17 1 4,6,3,5\n18 2 6,1\n").unwrap();
+    assert_eq!(
+        Check::Correct,
+        answers.check(17, 1, &Answer::from(vec![4, 6, 3, 5]))
+    );
+    assert_eq!(
+        Check::Correct,
+        answers.check(18, 2, &Answer::from(Position::at(6, 1)))
+    );
+}
+
+#[test]
+fn test_record() {
+    use std::time::Duration;
+
+    let mut answers = Answers::from_str("1 1 11\n").unwrap();
+    let outcome = |part, result| Outcome {
+        day: 1,
+        part,
+        result,
+        elapsed: Duration::ZERO,
+        check: Check::Unknown,
+    };
+
+    let mut outcomes = vec![outcome(1, Ok(12.into())), outcome(2, Ok(31.into()))];
+    answers.verify(&mut outcomes);
+
+    assert_eq!(1, answers.record(&outcomes));
+    assert_eq!("1 1 11\n1 2 31\n", answers.draw());
+}
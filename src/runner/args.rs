@@ -0,0 +1,312 @@
+use crate::runner::answers;
+use crate::runner::bench::BenchOptions;
+use crate::runner::registry::Filter;
+use crate::runner::InputSource;
+use crate::shared::log::{Level, LogConfig};
+use crate::shared::*;
+
+pub const USAGE: &str = "usage: aoc2024 [options] <day#> <part#|all>\n       \
+                         aoc2024 [options] all\n       \
+                         aoc2024 [options] bench <day#> <part#>\n       \
+                         aoc2024 list\n       \
+                         aoc2024 new <day#> [title]\n\
+                         \n\
+                         options:\n  \
+                           --input <file|->    read the puzzle input from a file, or - for stdin\n  \
+                           --answers <file>    known answers file (default inputs/answers.txt)\n  \
+                           --record            save answers which are not yet known\n  \
+                           --format <fmt>      table (default) or json, one JSON object per part\n  \
+                           --frames <path>     save a part's frames to an animated .png or a .ppm directory\n  \
+                           -q                  only log errors\n  \
+                           -v, -vv, -vvv       log info, debug or trace diagnostics\n  \
+                           --log <[day=]level> set the log level, globally or for a single day\n\
+                         \n\
+                         all options:\n  \
+                           --tag <tag>         only run days with this tag\n  \
+                           --skip-slow         leave out days which take a second or more\n\
+                         \n\
+                         bench options:\n  \
+                           --iters <n>         measured runs (default 10)\n  \
+                           --warmup <n>        unmeasured runs before measuring (default 2)\n  \
+                           --csv <file>        append results to a CSV file\n  \
+                           --label <text>      CSV label for the run (default git commit)";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Target {
+    All,
+    Day(u32, Parts),
+    Bench(u32, u32),
+    List,
+    // Generates the boilerplate for a new day, with its title.
+    New(u32, String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Parts {
+    One(u32),
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    // One JSON object per line for each part that was run.
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub target: Target,
+    pub input: InputSource,
+    pub answers: String,
+    pub record: bool,
+    pub format: Format,
+    // Where to save the frames of a single part's simulation.
+    pub frames: Option<String>,
+    pub filter: Filter,
+    pub bench: BenchOptions,
+    pub log: LogConfig,
+}
+
+impl Args {
+    // Parses the command line, excluding the program name.
+    pub fn parse(args: &[String]) -> Result<Args> {
+        let mut input = InputSource::Default;
+        let mut answers = answers::DEFAULT_PATH.to_owned();
+        let mut record = false;
+        let mut format = Format::Table;
+        let mut frames = None;
+        let mut filter = Filter::default();
+        let mut bench = BenchOptions::default();
+        let mut log = LogConfig::default();
+        let mut positional = Vec::new();
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .ok_or_else(|| Error::new(&format!("{} requires a value", arg)))
+            };
+
+            match arg.as_str() {
+                "--input" | "-i" => match value()?.as_str() {
+                    "-" => input = InputSource::Stdin,
+                    v => input = InputSource::File(v.to_owned()),
+                },
+                "--answers" => answers = value()?.clone(),
+                "--record" => record = true,
+                "--format" => {
+                    format = match value()?.as_str() {
+                        "table" => Format::Table,
+                        "json" => Format::Json,
+                        v => return Err(Error::new(&format!("unknown format {}", v))),
+                    }
+                }
+                "--frames" => frames = Some(value()?.clone()),
+                "--tag" => filter.tag = Some(value()?.clone()),
+                "--skip-slow" => filter.skip_slow = true,
+                "--iters" => bench.iters = value()?.parse::<usize>()?,
+                "--warmup" => bench.warmup = value()?.parse::<usize>()?,
+                "--csv" => bench.csv = Some(value()?.clone()),
+                "--label" => bench.label = Some(value()?.clone()),
+                "-q" => log.level = Level::Error,
+                "-v" => log.level = Level::Info,
+                "-vv" => log.level = Level::Debug,
+                "-vvv" => log.level = Level::Trace,
+                "--log" => log.apply(value()?)?,
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return Err(Error::new(&format!("unknown flag {}\n{}", flag, USAGE)));
+                }
+                _ => positional.push(arg.as_str()),
+            }
+        }
+
+        let target = match positional.as_slice() {
+            ["all"] => Target::All,
+            ["list"] => Target::List,
+            ["new", day] => Target::New(day.parse::<u32>()?, format!("Day {}", day)),
+            ["new", day, title] => Target::New(day.parse::<u32>()?, title.to_string()),
+            ["bench", day, part] => Target::Bench(day.parse::<u32>()?, parse_part(part)?),
+            [day, "all"] => Target::Day(day.parse::<u32>()?, Parts::Both),
+            [day, part] => Target::Day(day.parse::<u32>()?, Parts::One(parse_part(part)?)),
+            _ => return Err(Error::new(USAGE)),
+        };
+
+        if target == Target::All && input != InputSource::Default {
+            return Err(Error::new("--input cannot be used when running all days"));
+        }
+
+        if record && input != InputSource::Default {
+            return Err(Error::new(
+                "--record cannot be used with --input, answers are only kept for real inputs",
+            ));
+        }
+
+        if frames.is_some() && !matches!(target, Target::Day(_, Parts::One(_))) {
+            return Err(Error::new(
+                "--frames can only be used when running a single part",
+            ));
+        }
+
+        if target != Target::All && filter != Filter::default() {
+            return Err(Error::new(
+                "--tag and --skip-slow only apply when running all days",
+            ));
+        }
+
+        Ok(Args {
+            target,
+            input,
+            answers,
+            record,
+            format,
+            frames,
+            filter,
+            bench,
+            log,
+        })
+    }
+}
+
+fn parse_part(str: &str) -> Result<u32> {
+    match str.parse::<u32>()? {
+        part @ (1 | 2) => Ok(part),
+        _ => Err(Error::new("invalid part number")),
+    }
+}
+
+#[cfg(test)]
+fn parse(args: &[&str]) -> Result<Args> {
+    let owned: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+    Args::parse(&owned)
+}
+
+#[test]
+fn test_parse() {
+    let defaults = |target| Args {
+        target,
+        input: InputSource::Default,
+        answers: answers::DEFAULT_PATH.to_owned(),
+        record: false,
+        format: Format::Table,
+        frames: None,
+        filter: Filter::default(),
+        bench: BenchOptions::default(),
+        log: LogConfig::default(),
+    };
+
+    assert_eq!(
+        Ok(defaults(Target::Day(16, Parts::One(2)))),
+        parse(&["16", "2"])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            input: InputSource::File("other.txt".into()),
+            ..defaults(Target::Day(3, Parts::Both))
+        }),
+        parse(&["--input", "other.txt", "3", "all"])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            input: InputSource::Stdin,
+            ..defaults(Target::Day(3, Parts::One(1)))
+        }),
+        parse(&["3", "1", "-i", "-"])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            answers: "known.txt".into(),
+            record: true,
+            format: Format::Json,
+            ..defaults(Target::All)
+        }),
+        parse(&[
+            "--record",
+            "all",
+            "--answers",
+            "known.txt",
+            "--format",
+            "json"
+        ])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            bench: BenchOptions {
+                iters: 50,
+                warmup: 0,
+                csv: Some("bench.csv".into()),
+                label: None,
+            },
+            ..defaults(Target::Bench(11, 2))
+        }),
+        parse(&[
+            "bench",
+            "11",
+            "2",
+            "--iters",
+            "50",
+            "--warmup",
+            "0",
+            "--csv",
+            "bench.csv"
+        ])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            log: LogConfig {
+                level: Level::Debug,
+                targets: vec![("day16".into(), Level::Trace)],
+            },
+            ..defaults(Target::Day(16, Parts::One(1)))
+        }),
+        parse(&["-vv", "16", "1", "--log", "day16=trace"])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            filter: Filter {
+                tag: Some("grid".into()),
+                skip_slow: true,
+            },
+            ..defaults(Target::All)
+        }),
+        parse(&["all", "--tag", "grid", "--skip-slow"])
+    );
+
+    assert_eq!(
+        Ok(Args {
+            frames: Some("robots.png".into()),
+            ..defaults(Target::Day(14, Parts::One(2)))
+        }),
+        parse(&["14", "2", "--frames", "robots.png"])
+    );
+
+    assert_eq!(Ok(defaults(Target::List)), parse(&["list"]));
+    assert_eq!(
+        Ok(defaults(Target::New(21, "Day 21".into()))),
+        parse(&["new", "21"])
+    );
+    assert_eq!(
+        Ok(defaults(Target::New(21, "Keypad Conundrum".into()))),
+        parse(&["new", "21", "Keypad Conundrum"])
+    );
+
+    assert!(parse(&["3", "3"]).is_err());
+    assert!(parse(&["3", "1", "--skip-slow"]).is_err());
+    assert!(parse(&["bench", "3"]).is_err());
+    assert!(parse(&["bench", "3", "1", "--iters", "many"]).is_err());
+    assert!(parse(&["3"]).is_err());
+    assert!(parse(&["--input"]).is_err());
+    assert!(parse(&["--format", "xml", "all"]).is_err());
+    assert!(parse(&["--log", "day3=chatty", "3", "1"]).is_err());
+    assert!(parse(&["--input", "x.txt", "all"]).is_err());
+    assert!(parse(&["--record", "--input", "x.txt", "3", "1"]).is_err());
+    assert!(parse(&["3", "all", "-i", "-", "--record"]).is_err());
+    assert!(parse(&["14", "all", "--frames", "robots.png"]).is_err());
+    assert!(parse(&["--frames", "frames", "bench", "14", "2"]).is_err());
+}
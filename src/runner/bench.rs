@@ -0,0 +1,244 @@
+use crate::runner::format_duration;
+use crate::shared::*;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+pub const CSV_HEADER: &str = "label,day,part,phase,iters,min_us,median_us,mean_us,p95_us";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub iters: usize,
+    pub warmup: usize,
+    pub csv: Option<String>,
+    // Identifies the run in the CSV output, defaults to the current git commit.
+    pub label: Option<String>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            iters: 10,
+            warmup: 2,
+            csv: None,
+            label: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Stats {
+        debug_assert!(!samples.is_empty());
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        // Nearest-rank percentile.
+        let p95_rank = (n * 95).div_ceil(100).max(1);
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            p95: sorted[p95_rank - 1],
+        }
+    }
+}
+
+pub struct BenchResult {
+    pub day: u32,
+    pub part: u32,
+    pub iters: usize,
+    pub answer: Answer,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+// Parses and solves a part repeatedly, timing the two phases separately. The warmup runs are
+// not measured, and any error aborts the benchmark.
+pub fn bench(
+    day: u32,
+    part: u32,
+    solution: &dyn AnySolution,
+    input: &str,
+    opts: &BenchOptions,
+) -> Result<BenchResult> {
+    if opts.iters == 0 {
+        return Err(Error::new("bench requires at least one iteration"));
+    }
+
+    let mut parse_samples = Vec::with_capacity(opts.iters);
+    let mut solve_samples = Vec::with_capacity(opts.iters);
+    let mut answer = Answer::Text(String::new());
+
+    for i in 0..opts.warmup + opts.iters {
+        let start = Instant::now();
+        let parsed = solution.parse_any(input)?;
+        let parsed_at = Instant::now();
+        answer = solution.solve_any(part, parsed.as_ref())?;
+        let solved_at = Instant::now();
+
+        if i >= opts.warmup {
+            parse_samples.push(parsed_at - start);
+            solve_samples.push(solved_at - parsed_at);
+        }
+    }
+
+    Ok(BenchResult {
+        day,
+        part,
+        iters: opts.iters,
+        answer,
+        parse: Stats::of(&parse_samples),
+        solve: Stats::of(&solve_samples),
+    })
+}
+
+pub fn draw(result: &BenchResult) -> String {
+    let mut str = format!(
+        "day {} part {}: {} ({} iterations)\n\n",
+        result.day, result.part, result.answer, result.iters
+    );
+
+    str.push_str(&format!(
+        "{:<6} {:>10} {:>10} {:>10} {:>10}\n",
+        "phase", "min", "median", "mean", "p95"
+    ));
+    for (phase, stats) in [("parse", &result.parse), ("solve", &result.solve)] {
+        str.push_str(&format!(
+            "{:<6} {:>10} {:>10} {:>10} {:>10}\n",
+            phase,
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.mean),
+            format_duration(stats.p95),
+        ));
+    }
+
+    str
+}
+
+pub fn csv_rows(label: &str, result: &BenchResult) -> String {
+    let us = |d: Duration| format!("{:.3}", d.as_secs_f64() * 1_000_000.0);
+
+    let mut str = String::new();
+    for (phase, stats) in [("parse", &result.parse), ("solve", &result.solve)] {
+        str.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            label.replace(',', " "),
+            result.day,
+            result.part,
+            phase,
+            result.iters,
+            us(stats.min),
+            us(stats.median),
+            us(stats.mean),
+            us(stats.p95),
+        ));
+    }
+
+    str
+}
+
+// Appends the result to a CSV file, writing the header first if the file is new or empty.
+pub fn append_csv(path: &str, label: &str, result: &BenchResult) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+    file.write_all(csv_rows(label, result).as_bytes())?;
+
+    Ok(())
+}
+
+// The short hash of the current git commit, used as the default CSV label.
+pub fn git_label() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|v| v.trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+
+    let stats = Stats::of(&[ms(5), ms(1), ms(3), ms(2), ms(4)]);
+    assert_eq!(ms(1), stats.min);
+    assert_eq!(ms(3), stats.median);
+    assert_eq!(ms(3), stats.mean);
+    assert_eq!(ms(5), stats.p95);
+
+    let stats = Stats::of(&[ms(4), ms(1), ms(2), ms(3)]);
+    assert_eq!(Duration::from_micros(2_500), stats.median);
+
+    let samples: Vec<_> = (1..=100).map(ms).collect();
+    assert_eq!(ms(95), Stats::of(&samples).p95);
+
+    assert_eq!(ms(7), Stats::of(&[ms(7)]).p95);
+}
+
+#[cfg(test)]
+struct Counter;
+
+#[cfg(test)]
+impl Solution for Counter {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        Ok(input
+            .split_whitespace()
+            .map(|v| v.parse())
+            .collect::<std::result::Result<_, _>>()?)
+    }
+
+    fn part1(&self, input: &Vec<u32>) -> Result<Answer> {
+        Ok(input.iter().sum::<u32>().into())
+    }
+
+    fn part2(&self, _input: &Vec<u32>) -> Result<Answer> {
+        Err(Error::new("unsolved"))
+    }
+}
+
+#[test]
+fn test_bench() {
+    let opts = BenchOptions {
+        iters: 3,
+        warmup: 1,
+        ..Default::default()
+    };
+
+    let result = bench(4, 1, &Counter, "1 2 3", &opts).unwrap();
+    assert_eq!(Answer::Int(6), result.answer);
+    assert_eq!(3, result.iters);
+
+    let csv = csv_rows("abc123", &result);
+    let rows: Vec<_> = csv.lines().collect();
+    assert_eq!(2, rows.len());
+    assert!(rows[0].starts_with("abc123,4,1,parse,3,"));
+    assert!(rows[1].starts_with("abc123,4,1,solve,3,"));
+
+    assert!(bench(4, 2, &Counter, "1 2 3", &opts).is_err());
+    assert!(bench(4, 1, &Counter, "x", &opts).is_err());
+}
@@ -0,0 +1,173 @@
+use crate::runner::answers::Check;
+use crate::runner::Outcome;
+use crate::shared::*;
+use std::fmt::{Formatter, Write};
+
+// A minimal JSON value. Objects keep their keys in insertion order so the output is stable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Int(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Int(v) => write!(f, "{}", v),
+            Json::Str(v) => write_str(f, v),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_str(f: &mut Formatter<'_>, str: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in str.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::Str(value.to_owned())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => v.into(),
+            None => Json::Null,
+        }
+    }
+}
+
+// Integers become numbers, except those too large for an i64 which are kept as strings since
+// most JSON readers can't represent them. Positions become [x, y].
+impl From<&Answer> for Json {
+    fn from(value: &Answer) -> Self {
+        match value {
+            Answer::Int(v) => Json::Int(*v),
+            Answer::BigInt(v) => Json::Str(v.to_string()),
+            Answer::Text(v) => Json::Str(v.clone()),
+            Answer::Position(p) => Json::Array(vec![Json::Int(p.x as i64), Json::Int(p.y as i64)]),
+            Answer::List(items) => Json::Array(items.iter().map(Json::from).collect()),
+        }
+    }
+}
+
+// A single line describing one outcome. The keys are always present, in the same order, with
+// null for the ones which don't apply.
+pub fn outcome_line(outcome: &Outcome, input_path: &str) -> String {
+    let status = match (&outcome.result, &outcome.check) {
+        (Err(e), _) => match e.kind {
+            ErrorKind::Failed => "error",
+            ErrorKind::NotImplemented => "not_implemented",
+            ErrorKind::Panicked => "panicked",
+        },
+        (Ok(_), Check::Correct) => "correct",
+        (Ok(_), Check::Wrong { .. }) => "wrong",
+        (Ok(_), Check::Unknown) => "unknown",
+    };
+
+    let expected = match &outcome.check {
+        Check::Wrong { expected } => Some(expected.as_str()),
+        _ => None,
+    };
+
+    Json::Object(vec![
+        ("day", Json::Int(outcome.day as i64)),
+        ("part", Json::Int(outcome.part as i64)),
+        ("status", status.into()),
+        ("answer", outcome.result.as_ref().ok().into()),
+        ("expected", expected.into()),
+        (
+            "error",
+            outcome.result.as_ref().err().map(|e| e.msg.as_str()).into(),
+        ),
+        ("time_ns", Json::Int(outcome.elapsed.as_nanos() as i64)),
+        ("input", input_path.into()),
+    ])
+    .to_string()
+}
+
+#[test]
+fn test_encode() {
+    let value = Json::Object(vec![
+        (
+            "a",
+            Json::Array(vec![Json::Int(-1), "x".into(), Json::Null]),
+        ),
+        ("b", "say \"hi\"\n\t\\ \u{1}".into()),
+        ("c", Json::Object(vec![])),
+    ]);
+
+    assert_eq!(
+        r#"{"a":[-1,"x",null],"b":"say \"hi\"\n\t\\ \u0001","c":{}}"#,
+        value.to_string()
+    );
+}
+
+#[test]
+fn test_outcome_line() {
+    use std::time::Duration;
+
+    let outcome = Outcome {
+        day: 18,
+        part: 2,
+        result: Ok(Position::at(6, 1).into()),
+        elapsed: Duration::from_micros(1_500),
+        check: Check::Wrong {
+            expected: "6,2".into(),
+        },
+    };
+    assert_eq!(
+        r#"{"day":18,"part":2,"status":"wrong","answer":[6,1],"expected":"6,2","error":null,"time_ns":1500000,"input":"inputs/day18.txt"}"#,
+        outcome_line(&outcome, "inputs/day18.txt")
+    );
+
+    let outcome = Outcome {
+        day: 20,
+        part: 2,
+        result: Err(Error::not_implemented()),
+        elapsed: Duration::ZERO,
+        check: Check::Unknown,
+    };
+    assert_eq!(
+        r#"{"day":20,"part":2,"status":"not_implemented","answer":null,"expected":null,"error":"not implemented","time_ns":0,"input":"-"}"#,
+        outcome_line(&outcome, "-")
+    );
+}
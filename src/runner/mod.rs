@@ -0,0 +1,438 @@
+pub mod answers;
+pub mod args;
+pub mod bench;
+pub mod json;
+pub mod panics;
+pub mod registry;
+pub mod scaffold;
+
+use crate::runner::answers::Check;
+use crate::runner::registry::{Entry, Registry};
+use crate::shared::*;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    // inputs/dayN.txt, relative to the working directory.
+    Default,
+    File(String),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn path(&self, day: u32) -> String {
+        match self {
+            InputSource::Default => format!("inputs/day{}.txt", day),
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => "-".to_owned(),
+        }
+    }
+
+    pub fn read(&self, day: u32) -> Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            _ => {
+                let path = self.path(day);
+                std::fs::read_to_string(&path)
+                    .map_err(|e| Error::new(&format!("failed to read {}: {}", path, e)))
+            }
+        }
+    }
+}
+
+// The result of running a single part of a single day.
+pub struct Outcome {
+    pub day: u32,
+    pub part: u32,
+    pub result: Result<Answer>,
+    pub elapsed: Duration,
+    pub check: Check,
+}
+
+impl Outcome {
+    // Whether the part errored or panicked. Parts which are not implemented yet don't count.
+    pub fn is_failed(&self) -> bool {
+        matches!(&self.result, Err(e) if e.kind != ErrorKind::NotImplemented)
+    }
+
+    pub fn is_not_implemented(&self) -> bool {
+        matches!(&self.result, Err(e) if e.kind == ErrorKind::NotImplemented)
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self.check, Check::Wrong { .. })
+    }
+
+    pub fn status(&self) -> String {
+        match (&self.result, &self.check) {
+            (Err(e), _) => match e.kind {
+                ErrorKind::Failed => format!("error: {}", e),
+                ErrorKind::NotImplemented | ErrorKind::Panicked => e.to_string(),
+            },
+            (Ok(_), Check::Correct) => "correct".to_owned(),
+            (Ok(_), Check::Wrong { expected }) => format!("WRONG, expected {}", expected),
+            (Ok(_), Check::Unknown) => "unknown".to_owned(),
+        }
+    }
+}
+
+// Parses the input and solves the given part, timing both together. A panic in either is caught
+// and reported as the part's error.
+pub fn run_part(day: u32, part: u32, solution: &dyn AnySolution, input: &str) -> Outcome {
+    let start = Instant::now();
+
+    let result = panics::catch(|| {
+        solution
+            .parse_any(input)
+            .and_then(|parsed| solution.solve_any(part, parsed.as_ref()))
+    });
+
+    Outcome {
+        day,
+        part,
+        result,
+        elapsed: start.elapsed(),
+        check: Check::Unknown,
+    }
+}
+
+// An outcome for a part which was not run.
+fn not_run(day: u32, part: u32, err: Error) -> Outcome {
+    Outcome {
+        day,
+        part,
+        result: Err(err),
+        elapsed: Duration::ZERO,
+        check: Check::Unknown,
+    }
+}
+
+// Runs every requested part of every requested day in the given order. A failing part does not
+// stop the run, its error is recorded in the returned outcomes instead. Parts which the registry
+// doesn't list as solved are reported as not implemented without being run. Each day's input is
+// read once and shared between its parts.
+pub fn run_all(
+    registry: &Registry,
+    selected: &[u32],
+    parts: &[u32],
+    source: &InputSource,
+) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+
+    for day in selected {
+        let entry = match registry.get(*day) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        match source.read(*day) {
+            Ok(input) => {
+                for part in parts {
+                    outcomes.push(match entry.parts.contains(part) {
+                        true => run_part(*day, *part, entry.solution.as_ref(), &input),
+                        false => not_run(*day, *part, Error::not_implemented()),
+                    });
+                }
+            }
+            Err(e) => {
+                for part in parts {
+                    outcomes.push(not_run(*day, *part, e.clone()));
+                }
+            }
+        }
+    }
+
+    outcomes
+}
+
+// Runs a single part, whether or not the registry lists it as solved.
+pub fn run_one(entry: &Entry, part: u32, source: &InputSource) -> Outcome {
+    match source.read(entry.day) {
+        Ok(input) => run_part(entry.day, part, entry.solution.as_ref(), &input),
+        Err(e) => not_run(entry.day, part, e),
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.3}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.3}s", d.as_secs_f64())
+    }
+}
+
+pub fn draw_table(outcomes: &[Outcome]) -> String {
+    let rows: Vec<[String; 5]> = outcomes
+        .iter()
+        .map(|o| {
+            let answer = match &o.result {
+                Ok(v) => v.to_string(),
+                Err(_) => "-".to_owned(),
+            };
+
+            [
+                o.day.to_string(),
+                o.part.to_string(),
+                answer,
+                format_duration(o.elapsed),
+                o.status(),
+            ]
+        })
+        .collect();
+
+    let header = ["day", "part", "answer", "time", "status"];
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let mut str = String::new();
+    let mut push_row = |cells: [&str; 5]| {
+        let line = format!(
+            "{:>w0$}  {:>w1$}  {:<w2$}  {:>w3$}  {}",
+            cells[0],
+            cells[1],
+            cells[2],
+            cells[3],
+            cells[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        str.push_str(line.trim_end());
+        str.push('\n');
+    };
+
+    push_row(header);
+    for row in &rows {
+        push_row([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+    }
+
+    let total: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    let failed = outcomes.iter().filter(|o| o.is_failed()).count();
+    let not_implemented = outcomes.iter().filter(|o| o.is_not_implemented()).count();
+    let wrong = outcomes.iter().filter(|o| o.is_wrong()).count();
+    str.push_str(&format!(
+        "\n{} parts run, {} failed, {} not implemented, {} wrong, total time {}\n",
+        outcomes.len(),
+        failed,
+        not_implemented,
+        wrong,
+        format_duration(total)
+    ));
+
+    str
+}
+
+#[cfg(test)]
+struct Fixed(Result<Answer>, Result<Answer>);
+
+#[cfg(test)]
+impl Solution for Fixed {
+    type Input = ();
+
+    fn parse(&self, _input: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn part1(&self, _input: &()) -> Result<Answer> {
+        self.0.clone()
+    }
+
+    fn part2(&self, _input: &()) -> Result<Answer> {
+        self.1.clone()
+    }
+}
+
+#[cfg(test)]
+struct Echo;
+
+#[cfg(test)]
+impl Solution for Echo {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        match input.trim() {
+            "" => Err(Error::new("empty input")),
+            v => Ok(v.to_owned()),
+        }
+    }
+
+    fn part1(&self, input: &String) -> Result<Answer> {
+        Ok(input.clone().into())
+    }
+
+    fn part2(&self, input: &String) -> Result<Answer> {
+        Ok(input.chars().rev().collect::<String>().into())
+    }
+}
+
+#[test]
+fn test_run_part_passes_input() {
+    assert_eq!(Ok("abc".into()), run_part(1, 1, &Echo, "abc\n").result);
+    assert_eq!(Ok("cba".into()), run_part(1, 2, &Echo, "abc\n").result);
+    assert_eq!(
+        Err(Error::new("empty input")),
+        run_part(1, 2, &Echo, "\n").result
+    );
+    assert_eq!(
+        Err(Error::new("invalid part number 3")),
+        run_part(1, 3, &Echo, "abc").result
+    );
+}
+
+#[cfg(test)]
+struct Unfinished;
+
+#[cfg(test)]
+impl Solution for Unfinished {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        Ok(input.chars().map(|c| c.to_digit(10).unwrap()).collect())
+    }
+
+    fn part1(&self, input: &Vec<u32>) -> Result<Answer> {
+        Ok(input.iter().sum::<u32>().into())
+    }
+
+    fn part2(&self, _input: &Vec<u32>) -> Result<Answer> {
+        todo!()
+    }
+}
+
+#[test]
+fn test_run_part_catches_panics() {
+    assert_eq!(
+        Ok(Answer::Int(6)),
+        run_part(1, 1, &Unfinished, "123").result
+    );
+    assert_eq!(
+        "not implemented",
+        run_part(1, 2, &Unfinished, "123").status()
+    );
+
+    let outcome = run_part(1, 1, &Unfinished, "12x");
+    assert!(outcome.is_failed());
+    assert!(outcome.status().starts_with(
+        "panicked: called `Option::unwrap()` on a `None` value at src/runner/mod.rs:"
+    ));
+}
+
+#[test]
+fn test_input_source_path() {
+    assert_eq!("inputs/day7.txt", InputSource::Default.path(7));
+    assert_eq!("x.txt", InputSource::File("x.txt".into()).path(7));
+    assert_eq!("-", InputSource::Stdin.path(7));
+}
+
+#[cfg(test)]
+fn fixed_entry(day: u32, parts: &'static [u32], solution: Fixed) -> Entry {
+    Entry {
+        day,
+        title: "",
+        tags: &[],
+        runtime: registry::Runtime::Fast,
+        parts,
+        solution: Box::new(solution),
+    }
+}
+
+#[test]
+fn test_run_all_continues_past_failures() {
+    let mut registry = Registry::default();
+    registry.register(fixed_entry(
+        2,
+        &[1, 2],
+        Fixed(Ok("a".into()), Err(Error::new("boom"))),
+    ));
+    registry.register(fixed_entry(
+        1,
+        &[1, 2],
+        Fixed(Ok("b".into()), Ok("c".into())),
+    ));
+    registry.register(fixed_entry(3, &[1], Fixed(Ok("d".into()), Ok("e".into()))));
+
+    let source = InputSource::File("Cargo.toml".into());
+    let outcomes = run_all(&registry, &[1, 2, 3], &[1, 2], &source);
+
+    let summary: Vec<_> = outcomes
+        .iter()
+        .map(|o| (o.day, o.part, o.result.clone()))
+        .collect();
+    assert_eq!(
+        vec![
+            (1, 1, Ok("b".into())),
+            (1, 2, Ok("c".into())),
+            (2, 1, Ok("a".into())),
+            (2, 2, Err(Error::new("boom"))),
+            (3, 1, Ok("d".into())),
+            (3, 2, Err(Error::not_implemented())),
+        ],
+        summary
+    );
+
+    let outcome = run_one(registry.get(3).unwrap(), 2, &source);
+    assert_eq!(Ok("e".into()), outcome.result);
+}
+
+#[test]
+fn test_draw_table() {
+    let outcomes = vec![
+        Outcome {
+            day: 1,
+            part: 1,
+            result: Ok(12345.into()),
+            elapsed: Duration::from_micros(250),
+            check: Check::Correct,
+        },
+        Outcome {
+            day: 1,
+            part: 2,
+            result: Ok(7.into()),
+            elapsed: Duration::from_micros(20),
+            check: Check::Wrong {
+                expected: "8".into(),
+            },
+        },
+        Outcome {
+            day: 10,
+            part: 2,
+            result: Err(Error::new("no paths to end found")),
+            elapsed: Duration::from_micros(1_500),
+            check: Check::Unknown,
+        },
+        Outcome {
+            day: 20,
+            part: 2,
+            result: Err(Error::not_implemented()),
+            elapsed: Duration::from_micros(3),
+            check: Check::Unknown,
+        },
+    ];
+
+    let expected = [
+        "day  part  answer     time  status",
+        "  1     1  12345     250µs  correct",
+        "  1     2  7          20µs  WRONG, expected 8",
+        " 10     2  -       1.500ms  error: no paths to end found",
+        " 20     2  -           3µs  not implemented",
+        "",
+        "4 parts run, 1 failed, 1 not implemented, 1 wrong, total time 1.773ms",
+        "",
+    ];
+    assert_eq!(expected.join("\n"), draw_table(&outcomes));
+}
@@ -0,0 +1,98 @@
+use crate::shared::*;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    // Whether this thread is inside catch(), in which case panics are recorded instead of printed.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    // The message and location of the last panic recorded on this thread.
+    static LAST_PANIC: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+// Runs f, turning a panic into an error instead of unwinding further. A todo!() or
+// unimplemented!() becomes Error::not_implemented(), any other panic becomes Error::panicked()
+// with the message and source location of the panic.
+pub fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    install_hook();
+
+    let was_catching = CATCHING.with(|c| c.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(was_catching));
+
+    match result {
+        Ok(result) => result,
+        Err(payload) => {
+            let (message, location) = LAST_PANIC
+                .with(|p| p.borrow_mut().take())
+                .unwrap_or_else(|| (payload_message(payload.as_ref()), "unknown".to_owned()));
+
+            if message.starts_with("not yet implemented") || message.starts_with("not implemented")
+            {
+                Err(Error::not_implemented())
+            } else {
+                Err(Error::panicked(&message, &location))
+            }
+        }
+    }
+}
+
+// The hook is process wide, so panics outside of catch() are passed on to the previous hook to
+// keep the usual output for those.
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(|c| c.get()) {
+                let location = match info.location() {
+                    Some(l) => l.to_string(),
+                    None => "unknown".to_owned(),
+                };
+                let message = payload_message(info.payload());
+                LAST_PANIC.with(|p| *p.borrow_mut() = Some((message, location)));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(str) = payload.downcast_ref::<&str>() {
+        str.to_string()
+    } else if let Some(str) = payload.downcast_ref::<String>() {
+        str.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+#[test]
+fn test_catch() {
+    assert_eq!(Ok(3), catch(|| Ok(3)));
+    assert_eq!(
+        Err(Error::new("boom")),
+        catch::<()>(|| Err(Error::new("boom")))
+    );
+
+    assert_eq!(Err(Error::not_implemented()), catch::<()>(|| todo!()));
+    assert_eq!(
+        Err(Error::not_implemented()),
+        catch::<()>(|| unimplemented!("part 2"))
+    );
+
+    let line = line!() + 1;
+    let err = catch::<()>(|| panic!("unexpected char '{}'", '#')).unwrap_err();
+    assert_eq!(ErrorKind::Panicked, err.kind);
+    assert_eq!(
+        format!(
+            "panicked: unexpected char '#' at src/runner/panics.rs:{}:30",
+            line
+        ),
+        err.msg
+    );
+}
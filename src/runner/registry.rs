@@ -0,0 +1,260 @@
+use crate::shared::*;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    // Well under a second for both parts.
+    Fast,
+    // A second or more, these can be left out of a full run with --skip-slow.
+    Slow,
+}
+
+impl Runtime {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Runtime::Fast => "fast",
+            Runtime::Slow => "slow",
+        }
+    }
+}
+
+// A registered day, its solution and what we know about it.
+pub struct Entry {
+    pub day: u32,
+    pub title: &'static str,
+    pub tags: &'static [&'static str],
+    pub runtime: Runtime,
+    // The parts which are solved. Running all parts reports the others as not implemented
+    // without running them, asking for one explicitly still runs it.
+    pub parts: &'static [u32],
+    pub solution: Box<dyn AnySolution>,
+}
+
+// Restricts which days a full run includes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub tag: Option<String>,
+    pub skip_slow: bool,
+}
+
+impl Filter {
+    pub fn matches(&self, entry: &Entry) -> bool {
+        if self.skip_slow && entry.runtime == Runtime::Slow {
+            return false;
+        }
+
+        match &self.tag {
+            Some(tag) => entry.tags.contains(&tag.as_str()),
+            None => true,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    entries: BTreeMap<u32, Entry>,
+}
+
+impl Registry {
+    pub fn register(&mut self, entry: Entry) {
+        let day = entry.day;
+        if self.entries.insert(day, entry).is_some() {
+            panic!("day {} registered twice", day);
+        }
+    }
+
+    pub fn get(&self, day: u32) -> Result<&Entry> {
+        self.entries
+            .get(&day)
+            .ok_or_else(|| Error::new(&format!("day {day} not found")))
+    }
+
+    // The registered days matching the filter, in ascending order.
+    pub fn select(&self, filter: &Filter) -> Vec<u32> {
+        self.entries
+            .values()
+            .filter(|e| filter.matches(e))
+            .map(|e| e.day)
+            .collect()
+    }
+
+    pub fn draw(&self) -> String {
+        let rows: Vec<[String; 5]> = self
+            .entries
+            .values()
+            .map(|e| {
+                let parts: Vec<_> = e.parts.iter().map(|p| p.to_string()).collect();
+                [
+                    e.day.to_string(),
+                    e.title.to_owned(),
+                    parts.join(","),
+                    e.runtime.name().to_owned(),
+                    e.tags.join(", "),
+                ]
+            })
+            .collect();
+
+        let header = ["day", "title", "parts", "runtime", "tags"];
+        let mut widths = header.map(|h| h.chars().count());
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+
+        let mut str = String::new();
+        let mut push_row = |cells: [&str; 5]| {
+            let line = format!(
+                "{:>w0$}  {:<w1$}  {:<w2$}  {:<w3$}  {}",
+                cells[0],
+                cells[1],
+                cells[2],
+                cells[3],
+                cells[4],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            );
+            str.push_str(line.trim_end());
+            str.push('\n');
+        };
+
+        push_row(header);
+        for row in &rows {
+            push_row([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+        }
+
+        str
+    }
+}
+
+// Declares the day modules and a registry() function which registers each of them, so that adding
+// a day is a single entry:
+//
+//   registry! {
+//       1 => day1::Day1 {
+//           title: "Historian Hysteria",
+//           runtime: Fast,
+//           parts: [1, 2],
+//           tags: ["lists"],
+//       },
+//   }
+macro_rules! registry {
+    ($(
+        $day:literal => $module:ident::$solution:ident {
+            title: $title:literal,
+            runtime: $runtime:ident,
+            parts: [$($part:literal),*],
+            tags: [$($tag:literal),*] $(,)?
+        }
+    ),* $(,)?) => {
+        $(mod $module;)*
+
+        fn registry() -> $crate::runner::registry::Registry {
+            let mut registry = $crate::runner::registry::Registry::default();
+            $(
+                registry.register($crate::runner::registry::Entry {
+                    day: $day,
+                    title: $title,
+                    tags: &[$($tag),*],
+                    runtime: $crate::runner::registry::Runtime::$runtime,
+                    parts: &[$($part),*],
+                    solution: Box::new($module::$solution),
+                });
+            )*
+            registry
+        }
+    };
+}
+
+pub(crate) use registry;
+
+#[cfg(test)]
+struct Blank;
+
+#[cfg(test)]
+impl Solution for Blank {
+    type Input = ();
+
+    fn parse(&self, _input: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn part1(&self, _input: &()) -> Result<Answer> {
+        Ok(0.into())
+    }
+
+    fn part2(&self, _input: &()) -> Result<Answer> {
+        Err(Error::not_implemented())
+    }
+}
+
+#[cfg(test)]
+fn example() -> Registry {
+    let mut registry = Registry::default();
+    registry.register(Entry {
+        day: 12,
+        title: "Garden Groups",
+        tags: &["grid", "regions"],
+        runtime: Runtime::Fast,
+        parts: &[1, 2],
+        solution: Box::new(Blank),
+    });
+    registry.register(Entry {
+        day: 6,
+        title: "Guard Gallivant",
+        tags: &["grid", "simulation"],
+        runtime: Runtime::Slow,
+        parts: &[1],
+        solution: Box::new(Blank),
+    });
+    registry.register(Entry {
+        day: 3,
+        title: "Mull It Over",
+        tags: &["regex"],
+        runtime: Runtime::Fast,
+        parts: &[1, 2],
+        solution: Box::new(Blank),
+    });
+    registry
+}
+
+#[test]
+fn test_select() {
+    let registry = example();
+
+    assert_eq!(vec![3, 6, 12], registry.select(&Filter::default()));
+    assert_eq!(
+        vec![6, 12],
+        registry.select(&Filter {
+            tag: Some("grid".into()),
+            skip_slow: false,
+        })
+    );
+    assert_eq!(
+        vec![12],
+        registry.select(&Filter {
+            tag: Some("grid".into()),
+            skip_slow: true,
+        })
+    );
+
+    assert!(registry.get(6).is_ok());
+    assert_eq!(
+        Err(Error::new("day 7 not found")),
+        registry.get(7).map(|e| e.day)
+    );
+}
+
+#[test]
+fn test_draw() {
+    let expected = [
+        "day  title            parts  runtime  tags",
+        "  3  Mull It Over     1,2    fast     regex",
+        "  6  Guard Gallivant  1      slow     grid, simulation",
+        " 12  Garden Groups    1,2    fast     grid, regions",
+        "",
+    ];
+    assert_eq!(expected.join("\n"), example().draw());
+}
@@ -0,0 +1,169 @@
+use crate::shared::*;
+use std::fs;
+use std::path::Path;
+
+const TEMPLATE: &str = "use crate::shared::*;
+
+pub struct DayN;
+
+impl Solution for DayN {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(&self, _input: &String) -> Result<Answer> {
+        Err(Error::not_implemented())
+    }
+
+    fn part2(&self, _input: &String) -> Result<Answer> {
+        Err(Error::not_implemented())
+    }
+}
+
+#[test]
+fn test_part1() {
+    let example = read_example(\"inputs/dayN_example.txt\");
+    let input = DayN.parse(&example).unwrap();
+
+    assert_eq!(Err(Error::not_implemented()), DayN.part1(&input));
+}
+
+#[test]
+fn test_part2() {
+    let example = read_example(\"inputs/dayN_example.txt\");
+    let input = DayN.parse(&example).unwrap();
+
+    assert_eq!(Err(Error::not_implemented()), DayN.part2(&input));
+}
+";
+
+// Generates the module, an empty example input and a registry entry for a new day, relative to
+// the crate root. Returns the paths which were written. Nothing is written if the day already
+// has a module or a registry entry.
+pub fn new_day(root: &Path, day: u32, title: &str) -> Result<Vec<String>> {
+    let module = format!("day{}", day);
+    let module_path = root.join(format!("src/{}.rs", module));
+    let example_path = root.join(format!("inputs/{}_example.txt", module));
+    let main_path = root.join("src/main.rs");
+
+    if module_path.exists() || root.join(format!("src/{}/mod.rs", module)).exists() {
+        return Err(Error::new(&format!("day {} already exists", day)));
+    }
+
+    let main = fs::read_to_string(&main_path)?;
+    let main = register(&main, day, title)?;
+
+    let source = TEMPLATE
+        .replace("DayN", &format!("Day{}", day))
+        .replace("dayN", &module);
+
+    fs::write(&module_path, source)?;
+    fs::write(&main_path, main)?;
+
+    let mut written = vec![
+        module_path.display().to_string(),
+        main_path.display().to_string(),
+    ];
+
+    // The example may already have been saved, that's fine.
+    if !example_path.exists() {
+        fs::create_dir_all(root.join("inputs"))?;
+        fs::write(&example_path, "")?;
+        written.push(example_path.display().to_string());
+    }
+
+    Ok(written)
+}
+
+// Adds an entry for the day at the end of the registry! block in main.rs. The day has no solved
+// parts until its entry is updated.
+fn register(main: &str, day: u32, title: &str) -> Result<String> {
+    let start = main
+        .find("registry! {\n")
+        .ok_or_else(|| Error::new("registry! block not found in main.rs"))?;
+    let end = start
+        + main[start..]
+            .find("\n}\n")
+            .ok_or_else(|| Error::new("end of registry! block not found in main.rs"))?;
+
+    if main[start..end].contains(&format!("    {} => ", day)) {
+        return Err(Error::new(&format!("day {} is already registered", day)));
+    }
+
+    let entry = format!(
+        "\n    {day} => day{day}::Day{day} {{\n        \
+         title: {:?},\n        \
+         runtime: Fast,\n        \
+         parts: [],\n        \
+         tags: [],\n    \
+         }},",
+        title,
+        day = day
+    );
+
+    Ok(format!("{}{}{}", &main[..end], entry, &main[end..]))
+}
+
+#[test]
+fn test_register() {
+    let main = "mod runner;\n\
+                \n\
+                registry! {\n    \
+                    1 => day1::Day1 {\n        \
+                        title: \"Historian Hysteria\",\n        \
+                        runtime: Fast,\n        \
+                        parts: [1, 2],\n        \
+                        tags: [\"lists\"],\n    \
+                    },\n\
+                }\n\
+                \n\
+                fn main() {}\n";
+
+    let updated = register(main, 21, "Keypad Conundrum").unwrap();
+    assert!(updated.contains(
+        "        tags: [\"lists\"],\n    \
+         },\n    \
+         21 => day21::Day21 {\n        \
+         title: \"Keypad Conundrum\",\n        \
+         runtime: Fast,\n        \
+         parts: [],\n        \
+         tags: [],\n    \
+         },\n\
+         }\n\
+         \n\
+         fn main() {}\n"
+    ));
+
+    assert!(register(main, 1, "Again").is_err());
+    assert!(register("fn main() {}\n", 21, "").is_err());
+}
+
+#[test]
+fn test_new_day() {
+    let root = std::env::temp_dir().join(format!("aoc2024-scaffold-{}", std::process::id()));
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/main.rs"), "registry! {\n}\n").unwrap();
+
+    let written = new_day(&root, 21, "Day 21").unwrap();
+    assert_eq!(3, written.len());
+
+    let source = fs::read_to_string(root.join("src/day21.rs")).unwrap();
+    assert!(source.contains("pub struct Day21;"));
+    assert!(source.contains("read_example(\"inputs/day21_example.txt\")"));
+    assert!(root.join("inputs/day21_example.txt").exists());
+
+    let main = fs::read_to_string(root.join("src/main.rs")).unwrap();
+    assert!(main.contains("21 => day21::Day21 {"));
+
+    // A second attempt must leave everything as it is.
+    fs::write(root.join("src/day21.rs"), "edited").unwrap();
+    assert!(new_day(&root, 21, "Day 21").is_err());
+    assert_eq!(
+        "edited",
+        fs::read_to_string(root.join("src/day21.rs")).unwrap()
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,99 @@
+// A puzzle answer. Every variant has one canonical text form, which is what gets printed,
+// compared against known answers and written to the answers file.
+
+use crate::shared::*;
+use std::fmt::Formatter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    // For answers which don't fit an i64.
+    BigInt(i128),
+    Text(String),
+    // Formatted as "x,y".
+    Position(Position),
+    // Formatted with the items separated by commas.
+    List(Vec<Answer>),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::BigInt(v) => write!(f, "{}", v),
+            Answer::Text(v) => f.write_str(v),
+            Answer::Position(p) => write!(f, "{},{}", p.x, p.y),
+            Answer::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(v) => Answer::Int(v),
+                        Err(_) => Answer::BigInt(value as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, u32, i64, u64, usize);
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(v) => Answer::Int(v),
+            Err(_) => Answer::BigInt(value),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+impl From<Position> for Answer {
+    fn from(value: Position) -> Self {
+        Answer::Position(value)
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(value: Vec<T>) -> Self {
+        Answer::List(value.into_iter().map(|v| v.into()).collect())
+    }
+}
+
+#[test]
+fn test_format() {
+    assert_eq!("-12", Answer::from(-12).to_string());
+    assert_eq!("18446744073709551615", Answer::from(u64::MAX).to_string());
+    assert_eq!(Answer::BigInt(u64::MAX as i128), Answer::from(u64::MAX));
+    assert_eq!(Answer::Int(7), Answer::from(7usize));
+    assert_eq!("6,1", Answer::from(Position::at(6, 1)).to_string());
+    assert_eq!("4,6,3,5", Answer::from(vec![4u64, 6, 3, 5]).to_string());
+    assert_eq!("", Answer::from(Vec::<u64>::new()).to_string());
+    assert_eq!("abc", Answer::from("abc").to_string());
+}
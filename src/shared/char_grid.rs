@@ -1,174 +1,70 @@
 use crate::shared::*;
-use std::collections::HashSet;
-use std::fs::read_to_string;
 
-// A 2d grid of chars which can be loaded from a text file.
-#[derive(Debug, Clone)]
-pub struct CharGrid {
-    width: i32,
-    chars: Vec<char>,
-}
-
-impl CharGrid {
-    pub fn new(width: i32, height: i32) -> CharGrid {
-        debug_assert!(width >= 1);
-        debug_assert!(height >= 1);
+// A 2d grid of chars which can be parsed from text input.
+pub type CharGrid = Grid<char>;
 
-        let chars = vec![' '; (width * height) as usize];
-
-        CharGrid { width, chars }
-    }
+pub type CharGridView<'a> = GridView<'a, char>;
 
-    pub fn from_file(path: &str) -> Result<CharGrid> {
-        let contents = read_to_string(path)?;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch<'a> {
+    pub word: &'a str,
+    pub pos: Position,
+    pub dir: Direction,
+}
 
-        Self::from_str(&contents)
+impl Grid<char> {
+    pub fn new(width: i32, height: i32) -> CharGrid {
+        Grid::filled(width, height, ' ')
     }
 
     pub fn from_str(str: &str) -> Result<CharGrid> {
-        let mut width: i32 = 0;
-        let mut chars = Vec::new();
-
-        for line in str.lines() {
-            let trimmed = line.trim();
-            if width == 0 {
-                width = trimmed.len() as i32
-            } else if width != trimmed.len() as i32 {
-                return Err(Error::new("lines do not have equal length"));
-            }
-
-            for c in trimmed.chars() {
-                chars.push(c);
-            }
-        }
-
-        Ok(CharGrid { width, chars })
-    }
-
-    pub fn fill(&mut self, c: char) {
-        self.chars.fill(c);
-    }
-
-    pub fn width(&self) -> i32 {
-        self.width
-    }
-
-    pub fn x_max(&self) -> i32 {
-        self.width - 1
-    }
-
-    pub fn height(&self) -> i32 {
-        self.chars.len() as i32 / self.width()
-    }
-
-    pub fn y_max(&self) -> i32 {
-        self.height() - 1
-    }
-
-    pub fn set(&mut self, x: i32, y: i32, c: char) {
-        self.chars[((y * self.width) + x) as usize] = c
-    }
-
-    pub fn set_pos(&mut self, pos: Position, c: char) {
-        debug_assert!(
-            self.in_bounds(pos),
-            "set_pos called with out of bounds position {}",
-            pos
-        );
-        self.set(pos.x, pos.y, c)
-    }
-
-    pub fn at(&self, x: i32, y: i32) -> char {
-        self.chars[((y * self.width) + x) as usize]
-    }
-
-    pub fn at_pos(&self, pos: Position) -> char {
-        self.at(pos.x, pos.y)
-    }
-
-    pub fn find_one(&self, c: char) -> Result<(i32, i32)> {
-        let item = self.chars.iter().enumerate().find(|(idx, e)| **e == c);
-
-        match item {
-            Some((idx, _)) => Ok((idx as i32 % self.width, idx as i32 / self.width)),
-            None => Err(Error::new(&format!("char '{}' not found in CharGrid", c))),
-        }
-    }
-
-    pub fn find_one_pos(&self, c: char) -> Result<Position> {
-        self.find_one(c).map(|v| Position::at(v.0, v.1))
-    }
-
-    pub fn find_all_pos(&self, c: char) -> Vec<Position> {
-        self.chars
-            .iter()
-            .enumerate()
-            .filter(|(idx, e)| **e == c)
-            .map(|(idx, _)| Position {
-                x: idx as i32 % self.width,
-                y: idx as i32 / self.width,
-            })
-            .collect()
-    }
-
-    pub fn in_bounds(&self, p: Position) -> bool {
-        p.x >= 0 && p.y >= 0 && p.x <= self.x_max() && p.y <= self.y_max()
-    }
-
-    pub fn count(&self, c: char) -> usize {
-        self.chars.iter().filter(|v| **v == c).count()
+        Grid::parse(str, Ok)
     }
 
     pub fn uniq_chars(&self) -> Vec<char> {
-        let mut chars = HashSet::new();
-
-        for c in &self.chars {
-            chars.insert(*c);
-        }
-
-        chars.into_iter().collect()
+        self.uniq()
     }
 
-    pub fn line_direction(&self, mut x: i32, mut y: i32, dir: Direction) -> Vec<char> {
-        debug_assert!(x >= 0 && x <= self.x_max());
-        debug_assert!(y >= 0 && y <= self.y_max());
-
-        let mut vec = Vec::new();
-
-        loop {
-            vec.push(self.at(x, y));
+    pub fn draw(&self) -> String {
+        self.draw_with(|c| *c)
+    }
 
-            x += dir.0 as i32;
-            y += dir.1 as i32;
+    // Every occurrence of each word reading in any of the eight directions from its first char,
+    // in reading order of the first char. Palindromes are found once each way, and words of one
+    // char once in every direction.
+    pub fn find_words<'a>(&self, words: &[&'a str]) -> Vec<WordMatch<'a>> {
+        let words: Vec<(&str, Vec<char>)> =
+            words.iter().map(|w| (*w, w.chars().collect())).collect();
+        let mut found = Vec::new();
 
-            if x < 0 || y < 0 || x > self.x_max() || y > self.y_max() {
-                return vec;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pos = Position::at(x, y);
+
+                for &dir in ALL_EIGHT {
+                    for (word, chars) in &words {
+                        if self.reads(pos, dir, chars) {
+                            found.push(WordMatch { word, pos, dir });
+                        }
+                    }
+                }
             }
         }
-    }
 
-    pub fn windows(&self, width: i32, height: i32) -> Windows {
-        Windows {
-            cg: self,
-            x: 0,
-            y: 0,
-            width,
-            height,
-        }
+        found
     }
 
-    pub fn draw(&self) -> String {
-        let mut str = String::new();
+    fn reads(&self, pos: Position, dir: Direction, chars: &[char]) -> bool {
+        let mut pos = Some(pos);
 
-        for y in 0..self.height() {
-            for x in 0..self.width {
-                str.push(self.at(x, y))
+        for c in chars {
+            match pos {
+                Some(p) if self.at_pos(p) == *c => pos = self.step(p, dir),
+                _ => return false,
             }
-            str.push('\n')
         }
-        str.push('\n');
 
-        str
+        !chars.is_empty()
     }
 }
 
@@ -212,65 +108,69 @@ fn test_find_one() {
     assert!(cg.find_one('^').is_err());
 }
 
-// Windows is an iterator over a CharGrid that yields every permutation of the given size as a
-// read-only view.
-pub struct Windows<'a> {
-    cg: &'a CharGrid,
-    x: i32,
-    y: i32,
-    width: i32,
-    height: i32,
-}
-
-impl<'a> Iterator for Windows<'a> {
-    type Item = CharGridView<'a>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.x > self.cg.width() - self.width || self.y > self.cg.height() - self.height {
-            return None;
-        }
-
-        let result = Some(CharGridView {
-            cg: self.cg,
-            x_offset: self.x,
-            y_offset: self.y,
-            width: self.width,
-            height: self.height,
-        });
-
-        self.x += 1;
-        if self.x > self.cg.width() - self.width {
-            self.x = 0;
-            self.y += 1;
-        }
-
-        result
-    }
-}
-
-#[derive(Debug)]
-pub struct CharGridView<'a> {
-    cg: &'a CharGrid,
-    x_offset: i32,
-    y_offset: i32,
-    width: i32,
-    height: i32,
+#[test]
+fn test_find_words() {
+    let cg = CharGrid::from_str("XMASX\nSAMXM\nAXAMA\n").unwrap();
+
+    let found = cg.find_words(&["XMAS", "AXA"]);
+    assert_eq!(
+        vec![
+            WordMatch {
+                word: "XMAS",
+                pos: Position::at(0, 0),
+                dir: RIGHT
+            },
+            WordMatch {
+                word: "AXA",
+                pos: Position::at(2, 0),
+                dir: DOWN + RIGHT
+            },
+            WordMatch {
+                word: "XMAS",
+                pos: Position::at(3, 1),
+                dir: LEFT
+            },
+            WordMatch {
+                word: "AXA",
+                pos: Position::at(0, 2),
+                dir: RIGHT
+            },
+            WordMatch {
+                word: "AXA",
+                pos: Position::at(2, 2),
+                dir: LEFT
+            },
+            WordMatch {
+                word: "AXA",
+                pos: Position::at(4, 2),
+                dir: UP + LEFT
+            },
+        ],
+        found
+    );
+
+    // The diagonals of a grid taller than it is wide.
+    let cg = CharGrid::from_str("X..\n.M.\n..A\n.A.\nS..\n").unwrap();
+    let found = cg.find_words(&["XMAS"]);
+    assert_eq!(0, found.len());
+    let found = cg.find_words(&["AAS", "XMA"]);
+    assert_eq!(
+        vec![
+            (Position::at(0, 0), DOWN + RIGHT),
+            (Position::at(2, 2), DOWN + LEFT)
+        ],
+        found.iter().map(|m| (m.pos, m.dir)).collect::<Vec<_>>()
+    );
+
+    let wrapping = CharGrid::from_str("ASXM\n")
+        .unwrap()
+        .with_topology(Topology::WrapX);
+    assert_eq!(1, wrapping.find_words(&["XMAS"]).len());
+    assert!(cg.find_words(&[""]).is_empty());
 }
 
 impl CharGridView<'_> {
-    pub fn at(&self, x: i32, y: i32) -> char {
-        self.cg.at(x + self.x_offset, y + self.y_offset)
-    }
-
     pub fn chars(&self) -> Vec<char> {
-        let mut v = Vec::new();
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                v.push(self.at(x, y))
-            }
-        }
-
-        v
+        self.cells()
     }
 }
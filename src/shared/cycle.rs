@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where a deterministic simulation starts repeating itself: the state after prefix + period steps
+// is the same as the state after prefix steps, and so on forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    // The step before prefix + period with the same state as step n.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        match n < self.prefix {
+            true => n,
+            false => self.prefix + (n - self.prefix) % self.period,
+        }
+    }
+}
+
+// Watches the states of a simulation one step at a time, by a key which identifies each state,
+// and spots the first one which repeats.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+    seen: HashMap<K, usize>,
+}
+
+impl<K> Default for CycleDetector<K> {
+    fn default() -> Self {
+        CycleDetector {
+            seen: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> CycleDetector<K> {
+    // Records the state at the next step, the first being step 0. Returns the cycle once a state
+    // has been seen before.
+    pub fn observe(&mut self, key: K) -> Option<Cycle> {
+        let step = self.seen.len();
+
+        match self.seen.get(&key) {
+            Some(&prefix) => Some(Cycle {
+                prefix,
+                period: step - prefix,
+            }),
+            None => {
+                self.seen.insert(key, step);
+                None
+            }
+        }
+    }
+}
+
+// Brent's algorithm, which finds the same cycle while only keeping two states, at the cost of
+// stepping through the simulation more than once.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    // Find the period, by moving the tortoise up to the hare at each power of two until the hare
+    // comes round to meet it.
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start)?;
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare)?;
+        period += 1;
+    }
+
+    // Then the prefix, with the hare a period ahead of the tortoise so they meet where the cycle
+    // starts.
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(&hare)?;
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        prefix += 1;
+    }
+
+    Some(Cycle { prefix, period })
+}
+
+// The state after n steps, skipping over whole cycles once one is found. None if the simulation
+// ends before then. Like brent, only two states are kept, and steps short of the period are
+// simply stepped through.
+pub fn state_at<S: Clone + Eq>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    n: usize,
+) -> Option<S> {
+    if n == 0 {
+        return Some(start);
+    }
+
+    // Brent's search for the period, which stops early if step n comes first.
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start)?;
+    let mut at = 1;
+    while at < n && tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare)?;
+        period += 1;
+        at += 1;
+    }
+    if tortoise != hare {
+        return Some(hare);
+    }
+
+    // The states repeat every period from the tortoise's step on. That needn't be where the cycle
+    // first starts, but it's as good a place to skip from.
+    let cycle = Cycle {
+        prefix: at - period,
+        period,
+    };
+    let mut state = tortoise;
+    for _ in cycle.prefix..cycle.equivalent_step(n) {
+        state = step(&state)?;
+    }
+
+    Some(state)
+}
+
+#[cfg(test)]
+fn rho(n: &usize) -> Option<usize> {
+    // 0 1 2 3 4 5 6 4 5 6 ...
+    Some(match *n {
+        6 => 4,
+        n => n + 1,
+    })
+}
+
+#[cfg(test)]
+fn detect<K: Eq + Hash>(
+    step: impl Fn(&usize) -> Option<usize>,
+    key: impl Fn(usize) -> K,
+) -> Option<Cycle> {
+    let mut detector = CycleDetector::default();
+    let mut state = 0;
+
+    loop {
+        if let Some(cycle) = detector.observe(key(state)) {
+            return Some(cycle);
+        }
+        state = step(&state)?;
+    }
+}
+
+#[test]
+fn test_find_cycle() {
+    let expected = Some(Cycle {
+        prefix: 4,
+        period: 3,
+    });
+
+    assert_eq!(expected, detect(rho, |n| n));
+    assert_eq!(expected, brent(0, rho));
+    assert_eq!(expected, detect(rho, |n| n as u64));
+
+    let ends = |n: &usize| (*n < 10).then_some(n + 1);
+    assert_eq!(None, detect(ends, |n| n));
+    assert_eq!(None, brent(0, ends));
+
+    let fixed_point = |_: &usize| Some(3);
+    assert_eq!(
+        Some(Cycle {
+            prefix: 1,
+            period: 1
+        }),
+        brent(0, fixed_point)
+    );
+}
+
+#[test]
+fn test_state_at() {
+    assert_eq!(Some(0), state_at(0, rho, 0));
+    assert_eq!(Some(5), state_at(0, rho, 5));
+    assert_eq!(Some(4), state_at(0, rho, 7));
+    assert_eq!(Some(4), state_at(0, rho, 1_000_000_000_000));
+    assert_eq!(Some(5), state_at(0, rho, 1_000_000_000_001));
+    assert_eq!(None, state_at(0, |n| (*n < 10).then_some(n + 1), 11));
+    assert_eq!(Some(3), state_at(0, |_| Some(3), 1_000));
+
+    let cycle = brent(0, rho).unwrap();
+    assert_eq!(3, cycle.equivalent_step(3));
+    assert_eq!(4, cycle.equivalent_step(10));
+}
@@ -0,0 +1,152 @@
+use crate::shared::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// The distance from the closest of a set of sources to every cell of a grid, moving
+// orthogonally, along with which source that was. Cells which can't be reached have neither.
+#[derive(Debug, Clone)]
+pub struct DistanceField {
+    distances: Grid<Option<usize>>,
+    closest: Grid<Option<Position>>,
+}
+
+impl DistanceField {
+    pub fn distance(&self, pos: Position) -> Option<usize> {
+        self.distances.get(pos).copied().flatten()
+    }
+
+    // The source closest to pos. Ties go to the one which comes first in the sources.
+    pub fn closest_source(&self, pos: Position) -> Option<Position> {
+        self.closest.get(pos).copied().flatten()
+    }
+
+    // The distances as a layer the same shape as the grid, for use alongside it.
+    pub fn layer(&self) -> &Grid<Option<usize>> {
+        &self.distances
+    }
+
+    // Every reachable cell with its distance, in reading order.
+    pub fn reachable(&self) -> impl Iterator<Item = (Position, usize)> + '_ {
+        (0..self.distances.height()).flat_map(move |y| {
+            (0..self.distances.width()).filter_map(move |x| {
+                let pos = Position::at(x, y);
+                self.distance(pos).map(|d| (pos, d))
+            })
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    // Distances counting each step as 1, through the cells which are passable. Sources are
+    // always at distance 0, even if they aren't passable themselves.
+    pub fn distance_field(
+        &self,
+        sources: &[Position],
+        passable: impl Fn(&T) -> bool,
+    ) -> DistanceField {
+        self.weighted_distance_field(sources, |_, v| passable(v).then_some(1))
+    }
+
+    // Distances where cost gives the cost of stepping onto a cell, or None if it can't be
+    // entered.
+    pub fn weighted_distance_field(
+        &self,
+        sources: &[Position],
+        cost: impl Fn(Position, &T) -> Option<usize>,
+    ) -> DistanceField {
+        let mut distances = self.map(|_| None);
+        let mut closest = self.map(|_| None);
+
+        // Ordered by distance and then source, so the first source to reach a cell at its
+        // shortest distance claims it.
+        let mut queue = BinaryHeap::new();
+        for (index, source) in sources.iter().enumerate() {
+            if self.in_bounds(*source) {
+                queue.push(Reverse((0, index, source.x, source.y)));
+            }
+        }
+
+        while let Some(Reverse((distance, index, x, y))) = queue.pop() {
+            let pos = Position::at(x, y);
+            if distances.get(pos).unwrap().is_some() {
+                continue;
+            }
+            distances.set_pos(pos, Some(distance));
+            closest.set_pos(pos, Some(sources[index]));
+
+            for (next, v) in self.neighbours(pos, ORTHOGONAL) {
+                if distances.get(next).unwrap().is_none() {
+                    if let Some(step) = cost(next, v) {
+                        queue.push(Reverse((distance + step, index, next.x, next.y)));
+                    }
+                }
+            }
+        }
+
+        DistanceField { distances, closest }
+    }
+}
+
+#[test]
+fn test_distance_field() {
+    let cg = CharGrid::from_str("A..#.\n.#.#.\n...#B\n").unwrap();
+    let open = |c: &char| *c != '#';
+
+    let field = cg.distance_field(&[Position::at(0, 0)], open);
+    assert_eq!(Some(0), field.distance(Position::at(0, 0)));
+    assert_eq!(Some(4), field.distance(Position::at(2, 2)));
+    assert_eq!(None, field.distance(Position::at(4, 0)));
+    assert_eq!(None, field.distance(Position::at(3, 0)));
+    assert_eq!(None, field.distance(Position::at(9, 9)));
+    assert_eq!(8, field.reachable().count());
+
+    let sources = [Position::at(0, 0), Position::at(4, 2)];
+    let field = cg.distance_field(&sources, open);
+    assert_eq!(Some(2), field.distance(Position::at(4, 0)));
+    assert_eq!(
+        Some(Position::at(4, 2)),
+        field.closest_source(Position::at(4, 1))
+    );
+    assert_eq!(
+        Some(Position::at(0, 0)),
+        field.closest_source(Position::at(2, 2))
+    );
+    assert_eq!(None, field.closest_source(Position::at(1, 1)));
+    assert_eq!(
+        "012.2\n1.3.1\n234.0\n\n",
+        field
+            .layer()
+            .draw_with(|d| d.map_or('.', |d| char::from_digit(d as u32, 10).unwrap()))
+    );
+}
+
+#[test]
+fn test_closest_source_ties() {
+    let cg = CharGrid::from_str(".....\n").unwrap();
+
+    let field = cg.distance_field(&[Position::at(4, 0), Position::at(0, 0)], |_| true);
+    assert_eq!(
+        Some(Position::at(4, 0)),
+        field.closest_source(Position::at(2, 0))
+    );
+    assert_eq!(
+        Some(Position::at(0, 0)),
+        field.closest_source(Position::at(1, 0))
+    );
+}
+
+#[test]
+fn test_weighted_distance_field() {
+    let grid = Grid::parse("151\n111\n", |c| Ok(c.to_digit(10).unwrap() as usize)).unwrap();
+
+    // Going round the 5 is cheaper than going over it.
+    let field = grid.weighted_distance_field(&[Position::at(0, 0)], |_, v| Some(*v));
+    assert_eq!(Some(5), field.distance(Position::at(1, 0)));
+    assert_eq!(Some(4), field.distance(Position::at(2, 0)));
+
+    let field = grid.weighted_distance_field(&[Position::at(0, 0)], |p, v| {
+        (p != Position::at(1, 1)).then_some(*v)
+    });
+    assert_eq!(Some(6), field.distance(Position::at(2, 0)));
+    assert_eq!(None, field.distance(Position::at(1, 1)));
+}
@@ -0,0 +1,417 @@
+use crate::shared::*;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+// A 2d grid of cells of any type, stored row by row. CharGrid is a Grid<char>, and other
+// per-cell data such as a DistanceField's layer is kept in a Grid of the same shape alongside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: i32,
+    cells: Vec<T>,
+    topology: Topology,
+}
+
+impl<T> Grid<T> {
+    // Parses one row per line, converting each char into a cell.
+    pub fn parse(str: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Grid<T>> {
+        let mut width: i32 = 0;
+        let mut cells = Vec::new();
+
+        for line in str.lines() {
+            let trimmed = line.trim();
+            if width == 0 {
+                width = trimmed.len() as i32
+            } else if width != trimmed.len() as i32 {
+                return Err(Error::new("lines do not have equal length"));
+            }
+
+            for c in trimmed.chars() {
+                cells.push(cell(c)?);
+            }
+        }
+
+        Ok(Grid {
+            width,
+            cells,
+            topology: Topology::Bounded,
+        })
+    }
+
+    // Builds a grid by calling f with the x and y of every cell, row by row.
+    pub fn from_fn(width: i32, height: i32, mut f: impl FnMut(i32, i32) -> T) -> Grid<T> {
+        debug_assert!(width >= 1);
+        debug_assert!(height >= 1);
+
+        let mut cells = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+
+        Grid {
+            width,
+            cells,
+            topology: Topology::Bounded,
+        }
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Grid<T> {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn x_max(&self) -> i32 {
+        self.width - 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.cells.len() as i32 / self.width()
+    }
+
+    pub fn y_max(&self) -> i32 {
+        self.height() - 1
+    }
+
+    pub fn in_bounds(&self, p: Position) -> bool {
+        p.x >= 0 && p.y >= 0 && p.x <= self.x_max() && p.y <= self.y_max()
+    }
+
+    // The position on the grid which p refers to under its topology, or None if it is off a
+    // bounded edge.
+    pub fn wrap(&self, p: Position) -> Option<Position> {
+        self.topology
+            .wrap(p, Dimensions::of(self.width(), self.height()))
+    }
+
+    // The neighbouring position in the given direction, wrapping around the edges if the
+    // topology allows.
+    pub fn step(&self, p: Position, dir: Direction) -> Option<Position> {
+        self.wrap(p + dir)
+    }
+
+    fn index(&self, x: i32, y: i32) -> usize {
+        ((y * self.width) + x) as usize
+    }
+
+    fn position(&self, idx: usize) -> Position {
+        Position::at(idx as i32 % self.width, idx as i32 / self.width)
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, v: T) {
+        let idx = self.index(x, y);
+        self.cells[idx] = v
+    }
+
+    pub fn set_pos(&mut self, pos: Position, v: T) {
+        debug_assert!(
+            self.in_bounds(pos),
+            "set_pos called with out of bounds position {}",
+            pos
+        );
+        self.set(pos.x, pos.y, v)
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        match self.in_bounds(pos) {
+            true => Some(&self.cells[self.index(pos.x, pos.y)]),
+            false => None,
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+            topology: self.topology,
+        }
+    }
+
+    pub fn windows(&self, width: i32, height: i32) -> Windows<'_, T> {
+        Windows {
+            grid: self,
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    pub fn draw_with(&self, f: impl Fn(&T) -> char) -> String {
+        let mut str = String::new();
+
+        for y in 0..self.height() {
+            for x in 0..self.width {
+                str.push(f(&self.cells[self.index(x, y)]))
+            }
+            str.push('\n')
+        }
+        str.push('\n');
+
+        str
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: i32, height: i32, v: T) -> Grid<T> {
+        debug_assert!(width >= 1);
+        debug_assert!(height >= 1);
+
+        Grid {
+            width,
+            cells: vec![v; (width * height) as usize],
+            topology: Topology::Bounded,
+        }
+    }
+
+    pub fn fill(&mut self, v: T) {
+        self.cells.fill(v);
+    }
+
+    // The cells from (x, y) onwards in the given direction, until the edge of the grid or, if it
+    // wraps, until the line comes back around to where it started.
+    pub fn line_direction(&self, x: i32, y: i32, dir: Direction) -> Vec<T> {
+        debug_assert!(x >= 0 && x <= self.x_max());
+        debug_assert!(y >= 0 && y <= self.y_max());
+
+        let start = Position::at(x, y);
+        let mut pos = start;
+        let mut vec = Vec::new();
+
+        loop {
+            vec.push(self.cells[self.index(pos.x, pos.y)].clone());
+
+            match self.step(pos, dir) {
+                Some(next) if next != start => pos = next,
+                _ => return vec,
+            }
+        }
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn at(&self, x: i32, y: i32) -> T {
+        self.cells[self.index(x, y)]
+    }
+
+    pub fn at_pos(&self, pos: Position) -> T {
+        self.at(pos.x, pos.y)
+    }
+}
+
+impl<T: PartialEq + std::fmt::Debug> Grid<T> {
+    pub fn find_one(&self, v: T) -> Result<(i32, i32)> {
+        match self.cells.iter().position(|e| *e == v) {
+            Some(idx) => Ok((idx as i32 % self.width, idx as i32 / self.width)),
+            None => Err(Error::new(&format!("{:?} not found in grid", v))),
+        }
+    }
+
+    pub fn find_one_pos(&self, v: T) -> Result<Position> {
+        self.find_one(v).map(|v| Position::at(v.0, v.1))
+    }
+
+    pub fn find_all_pos(&self, v: T) -> Vec<Position> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| **e == v)
+            .map(|(idx, _)| self.position(idx))
+            .collect()
+    }
+
+    pub fn count(&self, v: T) -> usize {
+        self.cells.iter().filter(|e| **e == v).count()
+    }
+}
+
+impl<T: Eq + Hash + Clone> Grid<T> {
+    pub fn uniq(&self) -> Vec<T> {
+        let mut values = HashSet::new();
+
+        for v in &self.cells {
+            values.insert(v.clone());
+        }
+
+        values.into_iter().collect()
+    }
+}
+
+#[test]
+fn test_parse() {
+    let grid = Grid::parse("012\n345\n", |c| {
+        c.to_digit(10)
+            .ok_or_else(|| Error::new(&format!("not a digit: {}", c)))
+    })
+    .unwrap();
+
+    assert_eq!(3, grid.width());
+    assert_eq!(2, grid.height());
+    assert_eq!(5, grid.at(2, 1));
+    assert_eq!(Some(&3), grid.get(Position::at(0, 1)));
+    assert_eq!(None, grid.get(Position::at(3, 1)));
+    assert_eq!(Ok(Position::at(1, 1)), grid.find_one_pos(4));
+    assert_eq!(
+        "012\n345\n\n",
+        grid.draw_with(|d| (b'0' + *d as u8) as char)
+    );
+
+    assert!(Grid::parse("01\n2x\n", |c| c.to_digit(10).ok_or(Error::new("x"))).is_err());
+    assert!(Grid::parse("01\n2\n", Ok).is_err());
+}
+
+#[test]
+fn test_typed_cells() {
+    let mut grid: Grid<Option<usize>> = Grid::filled(3, 2, None);
+    grid.set_pos(Position::at(1, 1), Some(4));
+    grid.set(2, 0, Some(7));
+
+    assert_eq!(4, grid.count(None));
+    assert_eq!(vec![Position::at(2, 0)], grid.find_all_pos(Some(7)));
+    assert_eq!(
+        "..7\n.4.\n\n",
+        grid.draw_with(|v| match v {
+            Some(v) => char::from_digit(*v as u32, 10).unwrap(),
+            None => '.',
+        })
+    );
+
+    let sum = grid.map(|v| v.unwrap_or(0));
+    assert_eq!(11, sum.at(1, 1) + sum.at(2, 0) + sum.at(0, 0));
+}
+
+// Windows is an iterator over a Grid that yields every permutation of the given size as a
+// read-only view.
+pub struct Windows<'a, T> {
+    grid: &'a Grid<T>,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl<T> Windows<'_, T> {
+    // On a wrapping axis windows start at every cell, and run over the edge.
+    fn x_last(&self) -> i32 {
+        match self.grid.topology.wraps_x() {
+            true => self.grid.x_max(),
+            false => self.grid.width() - self.width,
+        }
+    }
+
+    fn y_last(&self) -> i32 {
+        match self.grid.topology.wraps_y() {
+            true => self.grid.y_max(),
+            false => self.grid.height() - self.height,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = GridView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x > self.x_last() || self.y > self.y_last() {
+            return None;
+        }
+
+        let result = Some(GridView {
+            grid: self.grid,
+            x_offset: self.x,
+            y_offset: self.y,
+            width: self.width,
+            height: self.height,
+        });
+
+        self.x += 1;
+        if self.x > self.x_last() {
+            self.x = 0;
+            self.y += 1;
+        }
+
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    x_offset: i32,
+    y_offset: i32,
+    width: i32,
+    height: i32,
+}
+
+impl<T> GridView<'_, T> {
+    // The grid position of the view's top left cell.
+    pub fn origin(&self) -> Position {
+        Position::at(self.x_offset, self.y_offset)
+    }
+
+    pub fn dims(&self) -> Dimensions {
+        Dimensions::of(self.width, self.height)
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> &T {
+        let pos = Position::at(x + self.x_offset, y + self.y_offset);
+        let pos = self.grid.wrap(pos).expect("GridView::get out of bounds");
+        &self.grid.cells[self.grid.index(pos.x, pos.y)]
+    }
+}
+
+impl<T: Copy> GridView<'_, T> {
+    pub fn at(&self, x: i32, y: i32) -> T {
+        *self.get(x, y)
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    pub fn cells(&self) -> Vec<T> {
+        let mut v = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                v.push(self.get(x, y).clone())
+            }
+        }
+
+        v
+    }
+}
+
+#[test]
+fn test_topology() {
+    let grid = Grid::parse("123\n456\n", |c| Ok(c.to_digit(10).unwrap())).unwrap();
+    assert_eq!(None, grid.step(Position::at(2, 0), RIGHT));
+    assert_eq!(vec![4, 5, 6], grid.line_direction(0, 1, RIGHT));
+
+    let grid = grid.with_topology(Topology::WrapX);
+    assert_eq!(
+        Some(Position::at(0, 0)),
+        grid.step(Position::at(2, 0), RIGHT)
+    );
+    assert_eq!(None, grid.step(Position::at(2, 0), UP));
+    assert_eq!(vec![5, 6, 4], grid.line_direction(1, 1, RIGHT));
+    assert_eq!(vec![2, 4], grid.line_direction(1, 0, DOWN + LEFT));
+
+    let windows: Vec<_> = grid.windows(2, 2).map(|v| v.cells()).collect();
+    assert_eq!(
+        vec![vec![1, 2, 4, 5], vec![2, 3, 5, 6], vec![3, 1, 6, 4]],
+        windows
+    );
+
+    let grid = grid.with_topology(Topology::Torus);
+    assert_eq!(
+        vec![1, 5, 3, 4, 2, 6],
+        grid.line_direction(0, 0, DOWN + RIGHT)
+    );
+    assert_eq!(6, grid.windows(2, 2).count());
+}
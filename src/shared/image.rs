@@ -0,0 +1,368 @@
+// Writes grids out as images, one square of pixels per cell, so that larger grids and
+// simulations can be looked at outside the terminal. PPM and PNG are both encoded here; the PNG
+// data is stored uncompressed, which every reader accepts.
+
+use crate::shared::*;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+pub const BLACK: Rgb = Rgb(0, 0, 0);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    // Draws each cell as a scale x scale square, coloured by the palette.
+    pub fn from_grid<T>(grid: &Grid<T>, scale: u32, palette: impl Fn(&T) -> Rgb) -> Image {
+        debug_assert!(scale >= 1);
+
+        let width = grid.width() as u32 * scale;
+        let height = grid.height() as u32 * scale;
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+
+        for y in 0..height {
+            for x in 0..width {
+                let pos = Position::at((x / scale) as i32, (y / scale) as i32);
+                pixels.push(palette(grid.get(pos).unwrap()));
+            }
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    // Binary PPM (P6).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for Rgb(r, g, b) in &self.pixels {
+            data.extend([*r, *g, *b]);
+        }
+
+        data
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = png_header(self.width, self.height);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&self.scanlines()));
+        write_chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+
+    // Writes a .ppm or .png file, depending on the extension.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let data = match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            Some("png") => self.to_png(),
+            _ => {
+                return Err(Error::new(&format!(
+                    "can't tell the image format of {}, use .ppm or .png",
+                    path.display()
+                )))
+            }
+        };
+
+        Ok(fs::write(path, data)?)
+    }
+
+    // The rows of RGB bytes, each preceded by PNG's "no filter" byte.
+    fn scanlines(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity((self.height * (1 + self.width * 3)) as usize);
+        for row in self.pixels.chunks(self.width as usize) {
+            data.push(0);
+            for Rgb(r, g, b) in row {
+                data.extend([*r, *g, *b]);
+            }
+        }
+
+        data
+    }
+}
+
+// Records the frames of a simulation, to be saved as an animated PNG or a directory of numbered
+// images.
+#[derive(Debug, Clone, Default)]
+pub struct Frames {
+    frames: Vec<Image>,
+}
+
+impl Frames {
+    pub fn push(&mut self, image: Image) {
+        self.frames.push(image)
+    }
+
+    // An animated PNG which loops forever, showing each frame for delay_ms. Every frame must be
+    // the same size.
+    pub fn to_apng(&self, delay_ms: u16) -> Result<Vec<u8>> {
+        let first = self
+            .frames
+            .first()
+            .ok_or_else(|| Error::new("no frames to animate"))?;
+        if self
+            .frames
+            .iter()
+            .any(|f| f.width != first.width || f.height != first.height)
+        {
+            return Err(Error::new("frames are not all the same size"));
+        }
+
+        let mut png = png_header(first.width, first.height);
+
+        let mut actl = Vec::new();
+        actl.extend((self.frames.len() as u32).to_be_bytes());
+        actl.extend(0u32.to_be_bytes());
+        write_chunk(&mut png, b"acTL", &actl);
+
+        // fcTL and fdAT chunks share one sequence. The first frame's data is a plain IDAT, so
+        // that readers without animation support show it.
+        let mut sequence: u32 = 0;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let mut fctl = Vec::new();
+            fctl.extend(sequence.to_be_bytes());
+            fctl.extend(frame.width.to_be_bytes());
+            fctl.extend(frame.height.to_be_bytes());
+            fctl.extend(0u32.to_be_bytes());
+            fctl.extend(0u32.to_be_bytes());
+            fctl.extend(delay_ms.to_be_bytes());
+            fctl.extend(1000u16.to_be_bytes());
+            fctl.extend([0, 0]);
+            write_chunk(&mut png, b"fcTL", &fctl);
+            sequence += 1;
+
+            let data = zlib_stored(&frame.scanlines());
+            if i == 0 {
+                write_chunk(&mut png, b"IDAT", &data);
+            } else {
+                let mut fdat = sequence.to_be_bytes().to_vec();
+                fdat.extend(data);
+                write_chunk(&mut png, b"fdAT", &fdat);
+                sequence += 1;
+            }
+        }
+
+        write_chunk(&mut png, b"IEND", &[]);
+
+        Ok(png)
+    }
+
+    // Writes an animated PNG if the path ends in .png, otherwise a directory of PPM frames.
+    // Returns the number of frames written.
+    pub fn save(&self, path: &Path) -> Result<usize> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => fs::write(path, self.to_apng(250)?)?,
+            _ => {
+                self.save_dir(path, "ppm")?;
+            }
+        }
+
+        Ok(self.frames.len())
+    }
+
+    // Writes frame_00000.<ext>, frame_00001.<ext> and so on into dir, creating it if needed.
+    // Returns the paths written.
+    pub fn save_dir(&self, dir: &Path, ext: &str) -> Result<Vec<String>> {
+        fs::create_dir_all(dir)?;
+
+        let mut written = Vec::new();
+        for (i, frame) in self.frames.iter().enumerate() {
+            let path = dir.join(format!("frame_{:05}.{}", i, ext));
+            frame.save(&path)?;
+            written.push(path.display().to_string());
+        }
+
+        Ok(written)
+    }
+}
+
+fn png_header(width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut ihdr = Vec::new();
+    ihdr.extend(width.to_be_bytes());
+    ihdr.extend(height.to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlacing.
+    ihdr.extend([8, 2, 0, 0, 0]);
+    write_chunk(&mut png, b"IHDR", &ihdr);
+
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+
+    let mut crc_data = kind.to_vec();
+    crc_data.extend(data);
+    png.extend(crc32(&crc_data).to_be_bytes());
+}
+
+// A zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[test]
+fn test_checksums() {
+    assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+    assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+}
+
+#[test]
+fn test_ppm() {
+    let cg = CharGrid::from_str("#.\n").unwrap();
+    let image = Image::from_grid(
+        &cg,
+        1,
+        |c| if *c == '#' { Rgb(255, 255, 255) } else { BLACK },
+    );
+
+    assert_eq!(
+        b"P6\n2 1\n255\n\xff\xff\xff\x00\x00\x00".to_vec(),
+        image.to_ppm()
+    );
+}
+
+#[test]
+fn test_png() {
+    let cg = CharGrid::from_str("#.\n.#\n").unwrap();
+    let image = Image::from_grid(&cg, 2, |c| if *c == '#' { Rgb(255, 0, 0) } else { BLACK });
+    assert_eq!((4, 4), (image.width, image.height));
+
+    let png = image.to_png();
+    assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+    assert_eq!(b"IHDR", &png[12..16]);
+    assert_eq!(b"IEND", &png[png.len() - 8..png.len() - 4]);
+
+    // The IDAT chunk holds the zlib header, one stored block of 4 rows of 1 + 4 * 3 bytes, and
+    // the checksum.
+    let idat = &png[33..];
+    assert_eq!(b"IDAT", &idat[4..8]);
+    assert_eq!(
+        2 + 5 + 4 * 13 + 4,
+        u32::from_be_bytes(idat[..4].try_into().unwrap())
+    );
+    assert_eq!([0, 255, 0, 0, 255, 0, 0, 0, 0, 0], idat[15..25]);
+}
+
+#[test]
+fn test_apng() {
+    let on = CharGrid::from_str("#\n").unwrap();
+    let off = CharGrid::from_str(".\n").unwrap();
+    let palette = |c: &char| if *c == '#' { Rgb(255, 255, 255) } else { BLACK };
+
+    let mut frames = Frames::default();
+    assert!(frames.to_apng(100).is_err());
+
+    for cg in [&on, &off, &on] {
+        frames.push(Image::from_grid(cg, 1, palette));
+    }
+    let apng = frames.to_apng(100).unwrap();
+
+    let kinds: Vec<&[u8]> = apng[8..]
+        .windows(4)
+        .filter(|w| {
+            [b"acTL", b"fcTL", b"IDAT", b"fdAT"]
+                .iter()
+                .any(|k| *w == &k[..])
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            &b"acTL"[..],
+            b"fcTL",
+            b"IDAT",
+            b"fcTL",
+            b"fdAT",
+            b"fcTL",
+            b"fdAT"
+        ],
+        kinds
+    );
+
+    frames.push(Image::from_grid(
+        &CharGrid::from_str("##\n").unwrap(),
+        1,
+        palette,
+    ));
+    assert!(frames.to_apng(100).is_err());
+}
+
+#[test]
+fn test_save_dir() {
+    let dir = std::env::temp_dir().join(format!("aoc2024-frames-{}", std::process::id()));
+    let cg = CharGrid::from_str("#.\n").unwrap();
+
+    let mut frames = Frames::default();
+    frames.push(Image::from_grid(&cg, 1, |_| BLACK));
+    frames.push(Image::from_grid(&cg, 1, |_| Rgb(255, 255, 255)));
+
+    let written = frames.save_dir(&dir, "ppm").unwrap();
+    assert_eq!(2, written.len());
+    assert!(written[1].ends_with("frame_00001.ppm"));
+    assert_eq!(
+        b"P6\n2 1\n255\n\xff\xff\xff\xff\xff\xff".to_vec(),
+        fs::read(dir.join("frame_00001.ppm")).unwrap()
+    );
+    assert!(frames.save_dir(&dir, "gif").is_err());
+
+    let png = dir.join("frames.png");
+    assert_eq!(2, frames.save(&png).unwrap());
+    assert_eq!(frames.to_apng(250).unwrap(), fs::read(&png).unwrap());
+    assert_eq!(2, frames.save(&dir.join("more")).unwrap());
+    assert!(dir.join("more").join("frame_00000.ppm").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,216 @@
+use crate::shared::*;
+
+// How a line moves between its points. Reduced visits every lattice point on the line, by
+// dividing the offset between two points by its gcd. Raw steps by the whole offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stepping {
+    Reduced,
+    Raw,
+}
+
+// The points on a segment or ray, in order from its start.
+#[derive(Debug, Clone)]
+pub struct Line {
+    next: Option<Position>,
+    step: Position,
+    // None for rays, which carry on until they leave the bounds, if they have any.
+    remaining: Option<usize>,
+    bounds: Option<(Position, Dimensions)>,
+}
+
+impl Line {
+    // Stops the line at the first point outside the bounds.
+    pub fn within(mut self, bounds: (Position, Dimensions)) -> Line {
+        self.bounds = Some(bounds);
+        self
+    }
+}
+
+impl Iterator for Line {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Position> {
+        let pos = self.next?;
+
+        if let Some((origin, dims)) = self.bounds {
+            if Topology::Bounded.wrap(pos.sub_pos(origin), dims).is_none() {
+                self.next = None;
+                return None;
+            }
+        }
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= 1;
+        }
+        // A ray without bounds stops rather than overflowing at the edge of the i32 range.
+        self.next = match self.remaining {
+            Some(0) => None,
+            _ => pos
+                .x
+                .checked_add(self.step.x)
+                .zip(pos.y.checked_add(self.step.y))
+                .map(|(x, y)| Position::at(x, y)),
+        };
+
+        Some(pos)
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+impl Position {
+    // The step from here towards other.
+    pub fn step_towards(&self, other: Position, stepping: Stepping) -> Position {
+        let offset = other.delta(*self);
+
+        match (stepping, gcd(offset.x, offset.y)) {
+            (Stepping::Raw, _) | (_, 0) => offset,
+            (Stepping::Reduced, d) => Position::at(offset.x / d, offset.y / d),
+        }
+    }
+
+    // The points from here to other, including both ends.
+    pub fn segment(&self, other: Position, stepping: Stepping) -> Line {
+        let step = self.step_towards(other, stepping);
+        let steps = match (step.x, step.y) {
+            (0, 0) => 0,
+            (0, dy) => (other.y - self.y) / dy,
+            (dx, _) => (other.x - self.x) / dx,
+        };
+
+        Line {
+            next: Some(*self),
+            step,
+            remaining: Some(steps as usize + 1),
+            bounds: None,
+        }
+    }
+
+    // The points from here through other and beyond, until they leave the bounds given with within
+    // or reach the edge of the i32 range. A ray through its own start is just that point.
+    pub fn ray(&self, through: Position, stepping: Stepping) -> Line {
+        let step = self.step_towards(through, stepping);
+
+        Line {
+            next: Some(*self),
+            step,
+            remaining: (step == Position::at(0, 0)).then_some(1),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // The first blocking cell looking from from through through, if there is one before the
+    // edge of the grid.
+    pub fn first_blocked(
+        &self,
+        from: Position,
+        through: Position,
+        blocks: impl Fn(&T) -> bool,
+    ) -> Option<Position> {
+        from.ray(through, Stepping::Reduced)
+            .within((
+                Position::at(0, 0),
+                Dimensions::of(self.width(), self.height()),
+            ))
+            .skip(1)
+            .find(|p| self.get(*p).is_some_and(&blocks))
+    }
+}
+
+#[test]
+fn test_segment() {
+    let from = Position::at(1, 1);
+
+    let points: Vec<_> = from
+        .segment(Position::at(7, 4), Stepping::Reduced)
+        .collect();
+    assert_eq!(
+        vec![
+            Position::at(1, 1),
+            Position::at(3, 2),
+            Position::at(5, 3),
+            Position::at(7, 4)
+        ],
+        points
+    );
+    assert_eq!(
+        vec![Position::at(1, 1), Position::at(7, 4)],
+        from.segment(Position::at(7, 4), Stepping::Raw)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        4,
+        from.segment(Position::at(1, -2), Stepping::Reduced).count()
+    );
+    assert_eq!(
+        vec![from],
+        from.segment(from, Stepping::Reduced).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_ray() {
+    let bounds = (Position::at(0, 0), Dimensions::of(10, 10));
+    let from = Position::at(4, 4);
+
+    let points: Vec<_> = from
+        .ray(Position::at(2, 8), Stepping::Reduced)
+        .within(bounds)
+        .collect();
+    assert_eq!(
+        vec![Position::at(4, 4), Position::at(3, 6), Position::at(2, 8)],
+        points
+    );
+
+    let points: Vec<_> = from
+        .ray(Position::at(6, 2), Stepping::Raw)
+        .within(bounds)
+        .collect();
+    assert_eq!(
+        vec![Position::at(4, 4), Position::at(6, 2), Position::at(8, 0)],
+        points
+    );
+
+    assert_eq!(
+        Some(Position::at(-2, -2)),
+        from.ray(Position::at(3, 3), Stepping::Reduced).nth(6)
+    );
+    assert_eq!(1, from.ray(from, Stepping::Raw).count());
+
+    let edge = Position::at(i32::MAX - 1, 0);
+    assert_eq!(
+        2,
+        edge.ray(Position::at(i32::MAX, 0), Stepping::Reduced)
+            .count()
+    );
+}
+
+#[test]
+fn test_first_blocked() {
+    let cg = CharGrid::from_str(".....\n..#..\n.....\n").unwrap();
+    let wall = |c: &char| *c == '#';
+
+    assert_eq!(
+        Some(Position::at(2, 1)),
+        cg.first_blocked(Position::at(0, 0), Position::at(2, 1), wall)
+    );
+    assert_eq!(
+        None,
+        cg.first_blocked(Position::at(0, 0), Position::at(4, 1), wall)
+    );
+    assert_eq!(
+        Some(Position::at(2, 1)),
+        cg.first_blocked(Position::at(4, 1), Position::at(3, 1), wall)
+    );
+    assert_eq!(
+        None,
+        cg.first_blocked(Position::at(4, 0), Position::at(3, 0), wall)
+    );
+}
@@ -0,0 +1,186 @@
+// Leveled diagnostics written to stderr, so they never mix with answers on stdout. Messages are
+// filtered by a global level, which can be overridden per target. A target is the module path of
+// the call site without the crate name, e.g. "day16" or "shared::char_grid".
+
+use crate::shared::*;
+use std::fmt::Arguments;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn from_str(str: &str) -> Result<Level> {
+        match str.to_ascii_lowercase().as_str() {
+            "off" => Ok(Level::Off),
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(Error::new(&format!("unknown log level {}", str))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Off => "OFF",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    pub level: Level,
+    // Per-target overrides, the most specific matching target wins.
+    pub targets: Vec<(String, Level)>,
+}
+
+const DEFAULT_CONFIG: LogConfig = LogConfig {
+    level: Level::Warn,
+    targets: Vec::new(),
+};
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG
+    }
+}
+
+impl LogConfig {
+    // Applies a "<target>=<level>" override, or sets the global level if no target is given.
+    pub fn apply(&mut self, directive: &str) -> Result<()> {
+        match directive.split_once('=') {
+            Some((target, level)) if !target.is_empty() => {
+                let level = Level::from_str(level)?;
+                self.targets.retain(|(t, _)| t != target);
+                self.targets.push((target.to_owned(), level));
+            }
+            Some(_) => return Err(Error::new(&format!("invalid log target {}", directive))),
+            None => self.level = Level::from_str(directive)?,
+        }
+
+        Ok(())
+    }
+
+    pub fn level_for(&self, target: &str) -> Level {
+        self.targets
+            .iter()
+            .filter(|(t, _)| {
+                target == t
+                    || (target.starts_with(t.as_str()) && target[t.len()..].starts_with("::"))
+            })
+            .max_by_key(|(t, _)| t.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+
+    fn max_level(&self) -> Level {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, Level::max)
+    }
+}
+
+static CONFIG: RwLock<LogConfig> = RwLock::new(DEFAULT_CONFIG);
+
+// The most verbose level enabled for any target, checked first so that disabled messages cost a
+// single atomic load.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+pub fn init(config: LogConfig) {
+    MAX_LEVEL.store(config.max_level() as u8, Ordering::Relaxed);
+    *CONFIG.write().unwrap() = config;
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    level <= CONFIG.read().unwrap().level_for(target(module_path))
+}
+
+pub fn write(level: Level, module_path: &str, args: Arguments) {
+    eprintln!("{:<5} {}: {}", level.name(), target(module_path), args);
+}
+
+fn target(module_path: &str) -> &str {
+    match module_path.split_once("::") {
+        Some((_, target)) => target,
+        None => module_path,
+    }
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::shared::log::enabled($level, module_path!()) {
+            $crate::shared::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::shared::log::log!($crate::shared::log::Level::Info, $($arg)+) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::shared::log::log!($crate::shared::log::Level::Debug, $($arg)+) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::shared::log::log!($crate::shared::log::Level::Trace, $($arg)+) };
+}
+
+// There are no error! or warn! shorthands as those names clash with the builtin lint
+// attributes, use log! with an explicit level instead.
+pub(crate) use {debug, info, log, trace};
+
+#[test]
+fn test_apply() {
+    let mut config = LogConfig::default();
+    config.apply("info").unwrap();
+    config.apply("day16=trace").unwrap();
+    config.apply("day2=off").unwrap();
+    config.apply("day16=debug").unwrap();
+
+    assert_eq!(
+        LogConfig {
+            level: Level::Info,
+            targets: vec![("day2".into(), Level::Off), ("day16".into(), Level::Debug)],
+        },
+        config
+    );
+    assert_eq!(Level::Debug, config.max_level());
+
+    assert!(config.apply("day16=loud").is_err());
+    assert!(config.apply("=trace").is_err());
+}
+
+#[test]
+fn test_level_for() {
+    let mut config = LogConfig::default();
+    config.apply("day1=trace").unwrap();
+    config.apply("shared=debug").unwrap();
+    config.apply("shared::char_grid=off").unwrap();
+
+    assert_eq!(Level::Trace, config.level_for("day1"));
+    assert_eq!(Level::Warn, config.level_for("day10"));
+    assert_eq!(Level::Debug, config.level_for("shared::meta_grid"));
+    assert_eq!(Level::Off, config.level_for("shared::char_grid"));
+    assert_eq!("day16", target("aoc2024::day16"));
+}
@@ -1,24 +1,77 @@
+mod answer;
 mod char_grid;
-mod meta_grid;
-
+mod cycle;
+mod distance_field;
+mod grid;
+mod image;
+mod line;
+pub mod log;
+mod neighbours;
+mod pattern;
+mod region;
+mod render;
+mod search;
+mod sparse_grid;
+mod topology;
+mod transform;
+
+pub use answer::*;
 pub use char_grid::*;
-pub use meta_grid::*;
-
+pub use cycle::*;
+pub use grid::*;
+pub use image::*;
+pub use line::*;
+pub(crate) use log::{debug, info, trace};
+pub use neighbours::*;
+pub use pattern::*;
+pub use region::*;
+pub use render::*;
+pub use search::*;
+pub use sparse_grid::*;
+pub use topology::*;
+
+use std::any::Any;
 use std::fmt::Formatter;
 use std::num::ParseIntError;
 use std::ops::Add;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Error {
     pub msg: String,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Failed,
+    // The part has not been written yet, which the runner reports separately from failures.
+    NotImplemented,
+    // The part panicked, the runner catches these so one part can't take down a whole run.
+    Panicked,
 }
 
 impl Error {
     pub fn new(string: &str) -> Error {
         Error {
             msg: string.to_owned(),
+            kind: ErrorKind::Failed,
+        }
+    }
+
+    // Returned by parts which have not been solved yet, in place of todo!().
+    pub fn not_implemented() -> Error {
+        Error {
+            msg: "not implemented".to_owned(),
+            kind: ErrorKind::NotImplemented,
+        }
+    }
+
+    pub fn panicked(message: &str, location: &str) -> Error {
+        Error {
+            msg: format!("panicked: {} at {}", message, location),
+            kind: ErrorKind::Panicked,
         }
     }
 }
@@ -39,31 +92,72 @@ impl std::error::Error for Error {}
 
 impl From<ParseIntError> for Error {
     fn from(value: ParseIntError) -> Self {
-        Error {
-            msg: format!("failed to parse int: {}", value.to_string()),
-        }
+        Error::new(&format!("failed to parse int: {}", value.to_string()))
     }
 }
 
 impl From<std::io::Error> for Error {
     fn from(value: std::io::Error) -> Self {
-        Error {
-            msg: format!("i/o error: {}", value.to_string()),
-        }
+        Error::new(&format!("i/o error: {}", value.to_string()))
     }
 }
 
 impl From<regex::Error> for Error {
     fn from(value: regex::Error) -> Self {
-        Error {
-            msg: format!("regex error: {}", value.to_string()),
-        }
+        Error::new(&format!("regex error: {}", value.to_string()))
     }
 }
 
+// A day's solution. Parsing is kept separate from solving so the runner can time them
+// independently, and both parts share the same parsed input.
 pub trait Solution {
-    fn part1(&self) -> Result<String>;
-    fn part2(&self) -> Result<String>;
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer>;
+
+    // The frames of a part's simulation, for days which have something worth looking at.
+    fn frames(&self, _part: u32, _input: &Self::Input) -> Result<Frames> {
+        Err(Error::new("no frames are recorded for this day"))
+    }
+}
+
+// An object safe view of a Solution with its input type erased, so that every day can be
+// stored together by the runner.
+pub trait AnySolution {
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn solve_any(&self, part: u32, input: &dyn Any) -> Result<Answer>;
+    fn frames_any(&self, part: u32, input: &dyn Any) -> Result<Frames>;
+}
+
+impl<S: Solution> AnySolution for S
+where
+    S::Input: 'static,
+{
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve_any(&self, part: u32, input: &dyn Any) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or_else(|| Error::new("solve_any called with input of the wrong type"))?;
+
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(Error::new(&format!("invalid part number {}", part))),
+        }
+    }
+
+    fn frames_any(&self, part: u32, input: &dyn Any) -> Result<Frames> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or_else(|| Error::new("frames_any called with input of the wrong type"))?;
+
+        self.frames(part, input)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -193,30 +287,6 @@ impl Position {
             y: self.y - other.y,
         }
     }
-
-    pub fn wrapping_add_direction(
-        &self,
-        rhs: Direction,
-        bounds: (Position, Dimensions),
-    ) -> Position {
-        let mut pos = self + rhs;
-
-        while pos.x < bounds.0.x {
-            pos.x += bounds.1.w;
-        }
-        while pos.y < bounds.0.y {
-            pos.y += bounds.1.h;
-        }
-
-        while pos.x >= bounds.0.x + bounds.1.w {
-            pos.x -= bounds.1.w;
-        }
-        while pos.y >= bounds.0.y + bounds.1.h {
-            pos.y -= bounds.1.h;
-        }
-
-        pos
-    }
 }
 
 impl std::fmt::Display for Position {
@@ -225,32 +295,6 @@ impl std::fmt::Display for Position {
     }
 }
 
-#[test]
-fn test_wrapping_add_direction() {
-    let bounds = (Position::at(0, 0), Dimensions::of(10, 10));
-
-    let mut pos = Position::at(0, 0);
-    pos = pos.wrapping_add_direction(Direction::of(-1, -1), bounds);
-
-    assert_eq!(Position::at(9, 9), pos);
-
-    let mut pos = Position::at(5, 5);
-    pos = pos.wrapping_add_direction(Direction::of(-100, 100), bounds);
-
-    assert_eq!(Position::at(5, 5), pos);
-
-    let bounds = (Position::at(0, 0), Dimensions::of(11, 7));
-    let mut pos = Position::at(10, 3);
-    pos = pos.wrapping_add_direction(Direction::of(1, 2), bounds);
-    assert_eq!(Position::at(0, 5), pos);
-
-    pos = pos.wrapping_add_direction(Direction::of(1, 2), bounds);
-    assert_eq!(Position::at(1, 0), pos);
-
-    pos = pos.wrapping_add_direction(Direction::of(1, 2), bounds);
-    assert_eq!(Position::at(2, 2), pos);
-}
-
 pub fn permutations<T: Clone>(n: usize, items: &[T]) -> Vec<Vec<T>> {
     fn append<T: Clone>(v: &Vec<T>, op: T) -> Vec<T> {
         let mut v = v.clone();
@@ -358,7 +402,7 @@ pub fn digits(n: usize) -> usize {
     else                        { 20 }
 }
 
-pub fn split_docs(s: String) -> Vec<String> {
+pub fn split_docs(s: &str) -> Vec<String> {
     let mut buf = String::new();
     let mut docs = Vec::new();
 
@@ -377,3 +421,9 @@ pub fn split_docs(s: String) -> Vec<String> {
 
     docs
 }
+
+// Reads an example input for use in tests, panicking with the path if it cannot be read.
+#[cfg(test)]
+pub fn read_example(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e))
+}
@@ -0,0 +1,114 @@
+use crate::shared::*;
+
+// Stencils, the offsets from a cell to the neighbours to visit. Any slice of directions works as
+// a stencil, such as knight moves.
+pub const ORTHOGONAL: &[Direction] = &[UP, RIGHT, DOWN, LEFT];
+pub const ALL_EIGHT: &[Direction] = &[
+    UP,
+    Direction(1, -1),
+    RIGHT,
+    Direction(1, 1),
+    DOWN,
+    Direction(-1, 1),
+    LEFT,
+    Direction(-1, -1),
+];
+
+// Iterates over the neighbours of a cell in stencil order, wrapping around the edges if the
+// grid's topology allows. Positions off a bounded edge are yielded with no cell.
+pub struct Neighbours<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Position,
+    stencil: &'a [Direction],
+    next: usize,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = (Position, Option<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dir = *self.stencil.get(self.next)?;
+        self.next += 1;
+
+        Some(match self.grid.step(self.pos, dir) {
+            Some(p) => (p, self.grid.get(p)),
+            None => (self.pos + dir, None),
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    // The neighbours of pos which are on the grid, with their cells.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Position,
+        stencil: &'a [Direction],
+    ) -> impl Iterator<Item = (Position, &'a T)> + 'a {
+        self.neighbours_all(pos, stencil)
+            .filter_map(|(p, v)| Some((p, v?)))
+    }
+
+    // Every neighbour of pos, with None for those off the edge of the grid.
+    pub fn neighbours_all<'a>(
+        &'a self,
+        pos: Position,
+        stencil: &'a [Direction],
+    ) -> Neighbours<'a, T> {
+        Neighbours {
+            grid: self,
+            pos,
+            stencil,
+            next: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+fn chars<'a>(neighbours: impl Iterator<Item = (Position, &'a char)>) -> String {
+    neighbours.map(|(_, c)| *c).collect()
+}
+
+#[test]
+fn test_neighbours() {
+    let cg = CharGrid::from_str("abc\ndef\nghi\n").unwrap();
+    let middle = Position::at(1, 1);
+
+    assert_eq!("bfhd", chars(cg.neighbours(middle, ORTHOGONAL)));
+    assert_eq!("bcfihgda", chars(cg.neighbours(middle, ALL_EIGHT)));
+    assert_eq!("feb", chars(cg.neighbours(Position::at(2, 0), ALL_EIGHT)));
+
+    let knight = [
+        Direction::of(1, 2),
+        Direction::of(2, 1),
+        Direction::of(-1, -2),
+    ];
+    assert_eq!("hf", chars(cg.neighbours(Position::at(0, 0), &knight)));
+}
+
+#[test]
+fn test_neighbours_all() {
+    let cg = CharGrid::from_str("abc\ndef\n").unwrap();
+
+    assert_eq!(
+        vec![
+            (Position::at(0, -1), None),
+            (Position::at(1, 0), Some(&'b')),
+            (Position::at(0, 1), Some(&'d')),
+            (Position::at(-1, 0), None),
+        ],
+        cg.neighbours_all(Position::at(0, 0), ORTHOGONAL)
+            .collect::<Vec<_>>()
+    );
+
+    let cg = cg.with_topology(Topology::WrapX);
+    assert_eq!(
+        vec![
+            (Position::at(0, -1), None),
+            (Position::at(1, 0), Some(&'b')),
+            (Position::at(0, 1), Some(&'d')),
+            (Position::at(2, 0), Some(&'c')),
+        ],
+        cg.neighbours_all(Position::at(0, 0), ORTHOGONAL)
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,268 @@
+use crate::shared::*;
+use std::collections::HashMap;
+
+// How a template was turned to make a match: flipped left to right first if flipped, then
+// rotated clockwise by the given number of quarter turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub quarter_turns: u8,
+    pub flipped: bool,
+}
+
+// Which orientations of a template are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientations {
+    Fixed,
+    Rotations,
+    // Rotations and reflections.
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    // The grid position of the template's top left cell, as oriented.
+    pub pos: Position,
+    pub orientation: Orientation,
+}
+
+// A small template to search grids for. Each char of the template is a key: the wildcard matches
+// any cell, and every other key matches cells for which its rule holds.
+pub struct Pattern<T> {
+    template: CharGrid,
+    wildcard: char,
+    rules: HashMap<char, Rule<T>>,
+    orientations: Orientations,
+}
+
+type Rule<T> = Box<dyn Fn(&T) -> bool>;
+
+// One orientation of the template, as the offsets of its non-wildcard cells.
+struct Oriented {
+    orientation: Orientation,
+    dims: Dimensions,
+    cells: Vec<(Position, char)>,
+}
+
+impl<T> Pattern<T> {
+    // Keys without a rule never match, so every key other than the wildcard needs one.
+    pub fn parse(template: &str, wildcard: char) -> Result<Pattern<T>> {
+        Ok(Pattern {
+            template: CharGrid::from_str(template)?,
+            wildcard,
+            rules: HashMap::new(),
+            orientations: Orientations::Fixed,
+        })
+    }
+
+    pub fn rule(mut self, key: char, f: impl Fn(&T) -> bool + 'static) -> Pattern<T> {
+        self.rules.insert(key, Box::new(f));
+        self
+    }
+
+    pub fn orientations(mut self, orientations: Orientations) -> Pattern<T> {
+        self.orientations = orientations;
+        self
+    }
+
+    // Each distinct orientation of the template. Symmetric templates look the same in more than
+    // one orientation, and only the first of those is kept so that matches aren't counted twice.
+    fn oriented(&self) -> Vec<Oriented> {
+        let flips: &[bool] = match self.orientations {
+            Orientations::All => &[false, true],
+            _ => &[false],
+        };
+        let turns = match self.orientations {
+            Orientations::Fixed => 1,
+            _ => 4,
+        };
+
+        let mut templates: Vec<(Orientation, CharGrid)> = Vec::new();
+        for &flipped in flips {
+            let mut template = match flipped {
+                true => self.template.flip_horizontal(),
+                false => self.template.clone(),
+            };
+            for quarter_turns in 0..turns {
+                if !templates.iter().any(|(_, t)| *t == template) {
+                    let orientation = Orientation {
+                        quarter_turns,
+                        flipped,
+                    };
+                    templates.push((orientation, template.clone()));
+                }
+                template = template.rotate_clockwise();
+            }
+        }
+
+        templates
+            .into_iter()
+            .map(|(orientation, template)| {
+                let mut cells = Vec::new();
+                for y in 0..template.height() {
+                    for x in 0..template.width() {
+                        if template.at(x, y) != self.wildcard {
+                            cells.push((Position::at(x, y), template.at(x, y)));
+                        }
+                    }
+                }
+
+                Oriented {
+                    orientation,
+                    dims: Dimensions::of(template.width(), template.height()),
+                    cells,
+                }
+            })
+            .collect()
+    }
+
+    // Only the template's non-wildcard cells are looked at, stopping at the first which fails.
+    fn matches_view(&self, oriented: &Oriented, view: &GridView<T>) -> bool {
+        oriented.cells.iter().all(|(offset, key)| {
+            self.rules
+                .get(key)
+                .is_some_and(|rule| rule(view.get(offset.x, offset.y)))
+        })
+    }
+
+    // Every match, by orientation and then from the top left of the grid. On grids which wrap,
+    // matches can run off one edge and back in on the other.
+    pub fn find(&self, grid: &Grid<T>) -> Vec<Match> {
+        let mut matches = Vec::new();
+
+        for oriented in self.oriented() {
+            for view in grid.windows(oriented.dims.w, oriented.dims.h) {
+                if self.matches_view(&oriented, &view) {
+                    matches.push(Match {
+                        pos: view.origin(),
+                        orientation: oriented.orientation,
+                    });
+                }
+            }
+        }
+
+        matches
+    }
+
+    // The orientations in which the template matches a window of exactly its size.
+    pub fn matches(&self, view: &GridView<T>) -> Vec<Orientation> {
+        self.oriented()
+            .into_iter()
+            .filter(|o| o.dims == view.dims() && self.matches_view(o, view))
+            .map(|o| o.orientation)
+            .collect()
+    }
+}
+
+impl Pattern<char> {
+    // A template of chars which match themselves, apart from the wildcard. Rules added later
+    // replace the literal match for their key.
+    pub fn chars(template: &str, wildcard: char) -> Result<Pattern<char>> {
+        let mut pattern = Pattern::parse(template, wildcard)?;
+        for key in pattern.template.uniq_chars() {
+            if key != wildcard {
+                pattern = pattern.rule(key, move |c| *c == key);
+            }
+        }
+
+        Ok(pattern)
+    }
+}
+
+#[test]
+fn test_fixed() {
+    let cg = CharGrid::from_str("ab.ab\n.ab..\n").unwrap();
+    let pattern = Pattern::chars("ab\n", '?').unwrap();
+
+    let found: Vec<_> = pattern.find(&cg).into_iter().map(|m| m.pos).collect();
+    assert_eq!(
+        vec![Position::at(0, 0), Position::at(3, 0), Position::at(1, 1)],
+        found
+    );
+
+    let pattern = Pattern::chars("ba\n", '?').unwrap();
+    assert!(pattern.find(&cg).is_empty());
+
+    let wrapping = cg.with_topology(Topology::WrapX);
+    assert_eq!(
+        vec![Position::at(4, 0)],
+        pattern
+            .find(&wrapping)
+            .into_iter()
+            .map(|m| m.pos)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_orientations() {
+    let cg = CharGrid::from_str("M.S\n.A.\nM.S\n").unwrap();
+
+    // An X-MAS looks the same flipped as it does turned half way round, so reflections add
+    // nothing.
+    let pattern = Pattern::chars("M?S\n?A?\nM?S\n", '?').unwrap();
+    assert_eq!(1, pattern.find(&cg).len());
+    assert_eq!(
+        1,
+        pattern
+            .orientations(Orientations::All)
+            .find(&cg.rotate_clockwise())
+            .len()
+    );
+
+    let pattern = Pattern::chars("M?M\n?A?\nS?S\n", '?')
+        .unwrap()
+        .orientations(Orientations::All);
+    assert_eq!(4, pattern.oriented().len());
+    assert_eq!(
+        vec![Match {
+            pos: Position::at(0, 0),
+            orientation: Orientation {
+                quarter_turns: 3,
+                flipped: false
+            }
+        }],
+        pattern.find(&cg)
+    );
+
+    let views: Vec<_> = cg.windows(3, 3).collect();
+    assert_eq!(
+        vec![Orientation {
+            quarter_turns: 3,
+            flipped: false
+        }],
+        pattern.matches(&views[0])
+    );
+
+    // An L has no symmetry, so all eight orientations are distinct.
+    let pattern = Pattern::chars("#..\n###\n", '?')
+        .unwrap()
+        .orientations(Orientations::All);
+    assert_eq!(8, pattern.oriented().len());
+}
+
+#[test]
+fn test_rules() {
+    let grid = Grid::parse("1234\n5678\n", |c| Ok(c.to_digit(10).unwrap())).unwrap();
+
+    let pattern = Pattern::parse("eo\n", '?')
+        .unwrap()
+        .rule('e', |n| n % 2 == 0)
+        .rule('o', |n| n % 2 == 1);
+    assert_eq!(
+        vec![Position::at(1, 0), Position::at(1, 1)],
+        pattern
+            .find(&grid)
+            .into_iter()
+            .map(|m| m.pos)
+            .collect::<Vec<_>>()
+    );
+
+    let missing_rule = Pattern::parse("ex\n", '?').unwrap().rule('e', |_| true);
+    assert!(missing_rule.find(&grid).is_empty());
+
+    let pattern = Pattern::chars("M?S\n", '?')
+        .unwrap()
+        .rule('M', |c| *c != 'S');
+    let cg = CharGrid::from_str("X.S\nS.S\n").unwrap();
+    assert_eq!(1, pattern.find(&cg).len());
+}
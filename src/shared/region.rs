@@ -0,0 +1,273 @@
+use crate::shared::*;
+use std::collections::HashSet;
+
+// Which neighbouring cells count as connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    // Up, down, left and right.
+    Four,
+    // Diagonals as well.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(&self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => ORTHOGONAL,
+            Connectivity::Eight => ALL_EIGHT,
+        }
+    }
+
+    // Gaps in a region have to be connected the other way: an 8-connected gap could slip between
+    // two 4-connected cells which only touch at their corners, and the other way around.
+    pub fn complement(&self) -> Connectivity {
+        match self {
+            Connectivity::Four => Connectivity::Eight,
+            Connectivity::Eight => Connectivity::Four,
+        }
+    }
+}
+
+// A set of connected cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Position>,
+    connectivity: Connectivity,
+}
+
+impl Region {
+    pub fn cells(&self) -> &HashSet<Position> {
+        &self.cells
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        self.cells.contains(&pos)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // The number of cell edges which don't border another cell in the region, including those
+    // around holes.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|p| {
+                ORTHOGONAL
+                    .iter()
+                    .filter(|dir| !self.contains(*p + **dir))
+                    .count()
+            })
+            .sum()
+    }
+
+    // Counts both the outward corners and the inward ones, where the outline turns back on
+    // itself, around the outside and any holes.
+    pub fn corners(&self) -> usize {
+        let turns = [(UP, RIGHT), (RIGHT, DOWN), (DOWN, LEFT), (LEFT, UP)];
+
+        self.cells
+            .iter()
+            .map(|p| {
+                turns
+                    .iter()
+                    .filter(|(a, b)| {
+                        let (a, b, diagonal) =
+                            (self.contains(*p + *a), self.contains(*p + *b), *a + *b);
+                        (!a && !b) || (a && b && !self.contains(*p + diagonal))
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    // The number of straight sections of the outline. Every side ends at a corner, so this is
+    // the same as the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    // The top left cell and the size of the smallest rectangle containing the region.
+    pub fn bounding_box(&self) -> (Position, Dimensions) {
+        let xs = self.cells.iter().map(|p| p.x);
+        let ys = self.cells.iter().map(|p| p.y);
+        let (x_min, x_max) = (xs.clone().min().unwrap(), xs.max().unwrap());
+        let (y_min, y_max) = (ys.clone().min().unwrap(), ys.max().unwrap());
+
+        (
+            Position::at(x_min, y_min),
+            Dimensions::of(x_max - x_min + 1, y_max - y_min + 1),
+        )
+    }
+
+    // The gaps which are entirely enclosed by the region.
+    pub fn holes(&self) -> Vec<Region> {
+        let bounds = self.bounding_box();
+        let (origin, dims) = bounds;
+        let on_edge = |p: &Position| {
+            p.x == origin.x
+                || p.y == origin.y
+                || p.x == origin.x + dims.w - 1
+                || p.y == origin.y + dims.h - 1
+        };
+
+        label_regions(
+            bounds,
+            self.connectivity.complement(),
+            |p| !self.contains(p),
+            |_, _| true,
+        )
+        .into_iter()
+        .filter(|gap| !gap.cells.iter().any(on_edge))
+        .collect()
+    }
+
+    pub fn has_holes(&self) -> bool {
+        !self.holes().is_empty()
+    }
+}
+
+// Labels the connected components of the cells within bounds for which include is true, where
+// joined decides whether two neighbouring included cells belong together. Every cell is visited
+// once.
+pub fn label_regions(
+    bounds: (Position, Dimensions),
+    connectivity: Connectivity,
+    include: impl Fn(Position) -> bool,
+    joined: impl Fn(Position, Position) -> bool,
+) -> Vec<Region> {
+    let (origin, dims) = bounds;
+    let mut labelled = Grid::filled(dims.w, dims.h, false);
+    let mut regions = Vec::new();
+
+    for y in 0..dims.h {
+        for x in 0..dims.w {
+            let start = Position::at(origin.x + x, origin.y + y);
+            if labelled.at(x, y) || !include(start) {
+                continue;
+            }
+
+            let mut cells = HashSet::from([start]);
+            let mut stack = vec![start];
+            labelled.set(x, y, true);
+
+            while let Some(pos) = stack.pop() {
+                for dir in connectivity.directions() {
+                    let next = pos + *dir;
+                    let local = next.sub_pos(origin);
+
+                    if labelled.get(local) == Some(&false) && include(next) && joined(pos, next) {
+                        labelled.set_pos(local, true);
+                        cells.insert(next);
+                        stack.push(next);
+                    }
+                }
+            }
+
+            regions.push(Region {
+                cells,
+                connectivity,
+            });
+        }
+    }
+
+    regions
+}
+
+impl<T> Grid<T> {
+    // The connected components of the cells matching the predicate.
+    pub fn regions_where(
+        &self,
+        connectivity: Connectivity,
+        include: impl Fn(&T) -> bool,
+    ) -> Vec<Region> {
+        label_regions(
+            (
+                Position::at(0, 0),
+                Dimensions::of(self.width(), self.height()),
+            ),
+            connectivity,
+            |p| include(self.get(p).unwrap()),
+            |_, _| true,
+        )
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    // The connected components of cells holding the same value, covering the whole grid.
+    pub fn regions(&self, connectivity: Connectivity) -> Vec<Region> {
+        label_regions(
+            (
+                Position::at(0, 0),
+                Dimensions::of(self.width(), self.height()),
+            ),
+            connectivity,
+            |_| true,
+            |a, b| self.get(a) == self.get(b),
+        )
+    }
+}
+
+#[cfg(test)]
+fn region_at(regions: &[Region], pos: Position) -> &Region {
+    regions.iter().find(|r| r.contains(pos)).unwrap()
+}
+
+#[test]
+fn test_regions() {
+    let cg = CharGrid::from_str("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+    let regions = cg.regions(Connectivity::Four);
+    assert_eq!(5, regions.len());
+
+    let a = region_at(&regions, Position::at(0, 0));
+    assert_eq!((4, 10, 4), (a.area(), a.perimeter(), a.sides()));
+
+    let c = region_at(&regions, Position::at(2, 1));
+    assert_eq!((4, 10, 8), (c.area(), c.perimeter(), c.sides()));
+    assert_eq!((Position::at(2, 1), Dimensions::of(2, 3)), c.bounding_box());
+
+    assert_eq!(1, region_at(&regions, Position::at(3, 1)).area());
+}
+
+#[test]
+fn test_connectivity() {
+    let cg = CharGrid::from_str("#..\n.#.\n..#\n").unwrap();
+
+    let four = cg.regions_where(Connectivity::Four, |c| *c == '#');
+    assert_eq!(3, four.len());
+
+    let eight = cg.regions_where(Connectivity::Eight, |c| *c == '#');
+    assert_eq!(1, eight.len());
+    assert_eq!(3, eight[0].area());
+    assert_eq!(12, eight[0].perimeter());
+    assert_eq!(12, eight[0].corners());
+}
+
+#[test]
+fn test_holes() {
+    let cg = CharGrid::from_str(
+        "OOOOO\n\
+         OXOXO\n\
+         OOOOO\n\
+         OXXOO\n",
+    )
+    .unwrap();
+    let regions = cg.regions(Connectivity::Four);
+
+    let o = region_at(&regions, Position::at(0, 0));
+    assert_eq!(2, o.holes().len());
+    // The outside has a notch cut out of it, and each hole has four sides of its own.
+    assert_eq!(8 + 4 + 4, o.sides());
+    assert!(o.holes().iter().all(|h| h.area() == 1));
+    assert!(!region_at(&regions, Position::at(1, 1)).has_holes());
+
+    // Only touching at the corners doesn't enclose the middle under 4-connectivity.
+    let cg = CharGrid::from_str(".#.\n#.#\n.#.\n").unwrap();
+    let diamond = cg.regions_where(Connectivity::Eight, |c| *c == '#');
+    assert_eq!(1, diamond.len());
+    assert_eq!(1, diamond[0].holes().len());
+    assert!(cg.regions_where(Connectivity::Four, |c| *c == '#')[0]
+        .holes()
+        .is_empty());
+}
@@ -0,0 +1,256 @@
+use crate::shared::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+    // One of the 256 colours of the extended palette.
+    Palette(u8),
+}
+
+impl Color {
+    fn escape(&self) -> String {
+        match self {
+            Color::Red => "\x1b[31m".to_owned(),
+            Color::Green => "\x1b[32m".to_owned(),
+            Color::Yellow => "\x1b[33m".to_owned(),
+            Color::Cyan => "\x1b[36m".to_owned(),
+            Color::Palette(n) => format!("\x1b[38;5;{}m", n),
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+// Blue through to red, for heatmaps.
+const HEAT: [Color; 6] = [
+    Color::Palette(21),
+    Color::Palette(51),
+    Color::Palette(46),
+    Color::Palette(226),
+    Color::Palette(208),
+    Color::Palette(196),
+];
+
+// A layer drawn over the grid. Cells can be recoloured, and optionally given a different char.
+struct Overlay {
+    name: String,
+    swatch: Vec<Color>,
+    cells: HashMap<Position, (Color, Option<char>)>,
+}
+
+// Draws a grid with overlays on top, later overlays over earlier ones, followed by a legend.
+pub struct Renderer {
+    base: CharGrid,
+    overlays: Vec<Overlay>,
+}
+
+impl Renderer {
+    pub fn new(cg: &CharGrid) -> Renderer {
+        Renderer {
+            base: cg.clone(),
+            overlays: Vec::new(),
+        }
+    }
+
+    pub fn from_fn(dims: Dimensions, f: impl Fn(Position) -> char) -> Renderer {
+        Renderer {
+            base: Grid::from_fn(dims.w, dims.h, |x, y| f(Position::at(x, y))),
+            overlays: Vec::new(),
+        }
+    }
+
+    fn overlay(
+        mut self,
+        name: &str,
+        swatch: Vec<Color>,
+        cells: impl IntoIterator<Item = (Position, (Color, Option<char>))>,
+    ) -> Renderer {
+        self.overlays.push(Overlay {
+            name: name.to_owned(),
+            swatch,
+            cells: cells
+                .into_iter()
+                .filter(|(p, _)| self.base.in_bounds(*p))
+                .collect(),
+        });
+        self
+    }
+
+    // Colours the given positions, keeping their chars.
+    pub fn highlight(
+        self,
+        name: &str,
+        color: Color,
+        positions: impl IntoIterator<Item = Position>,
+    ) -> Renderer {
+        let cells = positions.into_iter().map(|p| (p, (color, None)));
+        self.overlay(name, vec![color], cells)
+    }
+
+    // Replaces the chars at the given positions.
+    pub fn label(
+        self,
+        name: &str,
+        color: Color,
+        labels: impl IntoIterator<Item = (Position, char)>,
+    ) -> Renderer {
+        let cells = labels.into_iter().map(|(p, c)| (p, (color, Some(c))));
+        self.overlay(name, vec![color], cells)
+    }
+
+    // Draws an arrow on each step of the path pointing to the next one. The last position keeps
+    // its char.
+    pub fn path(self, name: &str, color: Color, path: &[Position]) -> Renderer {
+        let arrows = path.windows(2).map(|step| {
+            let arrow = match step[1].delta(step[0]) {
+                Position { x: 0, y: -1 } => '^',
+                Position { x: 1, y: 0 } => '>',
+                Position { x: 0, y: 1 } => 'v',
+                Position { x: -1, y: 0 } => '<',
+                _ => '*',
+            };
+            (step[0], (color, Some(arrow)))
+        });
+        let end = path.last().map(|p| (*p, (color, None)));
+
+        self.overlay(name, vec![color], arrows.chain(end).collect::<Vec<_>>())
+    }
+
+    // Colours each position with a value from blue for the lowest through to red for the
+    // highest.
+    pub fn heatmap(self, name: &str, value: impl Fn(Position) -> Option<usize>) -> Renderer {
+        let mut values = Vec::new();
+        for y in 0..self.base.height() {
+            for x in 0..self.base.width() {
+                let pos = Position::at(x, y);
+                if let Some(v) = value(pos) {
+                    values.push((pos, v));
+                }
+            }
+        }
+
+        let min = values.iter().map(|(_, v)| *v).min().unwrap_or(0);
+        let max = values.iter().map(|(_, v)| *v).max().unwrap_or(0);
+        let range = (max - min).max(1);
+
+        let cells: Vec<_> = values
+            .into_iter()
+            .map(|(p, v)| (p, (HEAT[(v - min) * (HEAT.len() - 1) / range], None)))
+            .collect();
+
+        self.overlay(&format!("{} {}..{}", name, min, max), HEAT.to_vec(), cells)
+    }
+
+    fn cell(&self, pos: Position) -> (char, Option<Color>) {
+        let mut cell = (self.base.at_pos(pos), None);
+
+        for overlay in &self.overlays {
+            if let Some((color, c)) = overlay.cells.get(&pos) {
+                cell = (c.unwrap_or(cell.0), Some(*color));
+            }
+        }
+
+        cell
+    }
+
+    pub fn render(&self) -> String {
+        self.draw(true)
+    }
+
+    // Without colour only the chars which overlays replace show, which keeps tests readable.
+    fn draw(&self, colored: bool) -> String {
+        let mut str = String::new();
+
+        for y in 0..self.base.height() {
+            let mut current = None;
+            for x in 0..self.base.width() {
+                let (c, color) = self.cell(Position::at(x, y));
+                if colored && color != current {
+                    str.push_str(&color.map_or(RESET.to_owned(), |c| c.escape()));
+                    current = color;
+                }
+                str.push(c);
+            }
+            if current.is_some() {
+                str.push_str(RESET);
+            }
+            str.push('\n');
+        }
+        str.push('\n');
+
+        for overlay in &self.overlays {
+            for color in &overlay.swatch {
+                match colored {
+                    true => str.push_str(&format!("{}■{}", color.escape(), RESET)),
+                    false => str.push('■'),
+                }
+            }
+            str.push_str(&format!(" {}\n", overlay.name));
+        }
+
+        str
+    }
+}
+
+#[test]
+fn test_render_uncolored() {
+    let cg = CharGrid::from_str("....\n.#..\n....\n").unwrap();
+
+    let renderer = Renderer::new(&cg)
+        .path(
+            "route",
+            Color::Green,
+            &[
+                Position::at(0, 0),
+                Position::at(1, 0),
+                Position::at(2, 0),
+                Position::at(2, 1),
+            ],
+        )
+        .label(
+            "marks",
+            Color::Red,
+            [(Position::at(3, 2), 'X'), (Position::at(9, 9), 'Y')],
+        )
+        .highlight("wall", Color::Yellow, [Position::at(1, 1)]);
+
+    assert_eq!(
+        ">>v.\n\
+         .#..\n\
+         ...X\n\
+         \n\
+         ■ route\n\
+         ■ marks\n\
+         ■ wall\n",
+        renderer.draw(false)
+    );
+}
+
+#[test]
+fn test_render_colored() {
+    let cg = CharGrid::from_str("ab\ncd\n").unwrap();
+
+    let renderer = Renderer::new(&cg).highlight("x", Color::Red, [Position::at(1, 0)]);
+    assert_eq!(
+        "a\x1b[31mb\x1b[0m\ncd\n\n\x1b[31m■\x1b[0m x\n",
+        renderer.render()
+    );
+}
+
+#[test]
+fn test_heatmap() {
+    let renderer =
+        Renderer::from_fn(Dimensions::of(3, 1), |_| '.').heatmap("cost", |p| match p.x {
+            0 => None,
+            x => Some(x as usize * 10),
+        });
+
+    assert!(renderer
+        .render()
+        .starts_with(".\x1b[38;5;21m.\x1b[38;5;196m.\x1b[0m\n\n"));
+    assert!(renderer.draw(false).ends_with(" cost 10..20\n"));
+}
@@ -0,0 +1,428 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// The result of a search: the distance to every state which was reached, the states each was
+// reached from along a shortest path, and the goals which were found. Searches stop as soon as a
+// goal is taken off the queue, pass |_| false as the goal to explore everything reachable.
+//
+// The _all variants keep every predecessor on a shortest path rather than just the first, and
+// keep going until every goal at the shortest distance has been found, so that all the optimal
+// paths can be counted. Steps must cost more than zero for these.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    distances: HashMap<S, usize>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+    all: bool,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn start(start: S, all: bool) -> Search<S> {
+        Search {
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+            goals: Vec::new(),
+            all,
+        }
+    }
+
+    // Records that next can be reached from state at the given distance, returning whether it
+    // needs to be (re)visited.
+    fn reach(&mut self, state: &S, next: &S, distance: usize) -> bool {
+        match self.distance(next) {
+            Some(d) if d < distance => false,
+            Some(d) if d == distance => {
+                if self.all {
+                    self.predecessors.get_mut(next).unwrap().push(state.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(next.clone(), distance);
+                self.predecessors.insert(next.clone(), vec![state.clone()]);
+                true
+            }
+        }
+    }
+
+    // Whether a state at this distance can be the first goal found, or another one as short.
+    fn wants_goal(&self, distance: usize) -> bool {
+        match self.goal_distance() {
+            None => true,
+            Some(d) => self.all && distance == d,
+        }
+    }
+
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn goal_distance(&self) -> Option<usize> {
+        self.goal().and_then(|g| self.distance(g))
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn predecessor(&self, state: &S) -> Option<&S> {
+        self.predecessors(state).first()
+    }
+
+    // Every state which state can be reached from along a shortest path. Only the first unless
+    // this is the result of one of the _all searches.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], |p| p.as_slice())
+    }
+
+    // The states from the start to the goal inclusive, or None if no goal was found.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal()?)
+    }
+
+    // The states from the start to the given state inclusive, or None if it wasn't reached.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.distance(state)?;
+
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.predecessor(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    // The number of shortest paths from the start to any of the goals.
+    pub fn count_paths(&self) -> usize {
+        let mut states: Vec<_> = self.on_any_path().into_iter().collect();
+        states.sort_by_key(|s| self.distances[s]);
+
+        // Every predecessor is closer to the start, so its count is known by the time it's needed.
+        let mut counts: HashMap<S, usize> = HashMap::new();
+        for state in states {
+            let count = match self.predecessors(&state) {
+                [] => 1,
+                preds => preds.iter().map(|p| counts[p]).sum(),
+            };
+            counts.insert(state, count);
+        }
+
+        self.goals.iter().map(|g| counts[g]).sum()
+    }
+
+    // Every state on at least one shortest path from the start to a goal.
+    pub fn on_any_path(&self) -> HashSet<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut queue = self.goals.clone();
+
+        while let Some(state) = queue.pop() {
+            for pred in self.predecessors(&state) {
+                if states.insert(pred.clone()) {
+                    queue.push(pred.clone());
+                }
+            }
+        }
+
+        states
+    }
+}
+
+// Breadth first search where every step costs 1.
+pub fn bfs_all<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(start, neighbours, is_goal, true)
+}
+
+fn breadth_first<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    all: bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::start(start.clone(), all);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let distance = search.distances[&state];
+        if !search.wants_goal(distance) {
+            break;
+        }
+
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for next in neighbours(&state) {
+            if search.reach(&state, &next, distance + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+// Shortest paths where neighbours are given with the cost of the step to them.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    best_first(start, neighbours, |_| 0, is_goal, false)
+}
+
+pub fn dijkstra_all<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    best_first(start, neighbours, |_| 0, is_goal, true)
+}
+
+// Dijkstra guided by an estimate of the remaining cost to a goal. The estimate must never be more
+// than the real cost, or the path found may not be the shortest.
+pub fn astar<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> usize,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    best_first(start, neighbours, heuristic, is_goal, false)
+}
+
+fn best_first<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+    all: bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut search = Search::start(start.clone(), all);
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Queued {
+        priority,
+        cost,
+        state,
+    }) = queue.pop()
+    {
+        if !search.wants_goal(priority) {
+            break;
+        }
+
+        // Already reached more cheaply since this was queued.
+        if cost > search.distances[&state] {
+            continue;
+        }
+
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if !search.reach(&state, &next, next_cost) {
+                continue;
+            }
+
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    search
+}
+
+// An entry in the priority queue, ordered so that the lowest priority is popped first.
+struct Queued<S> {
+    priority: usize,
+    cost: usize,
+    state: S,
+}
+
+impl<S> PartialEq for Queued<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S> Eq for Queued<S> {}
+
+impl<S> Ord for Queued<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S> PartialOrd for Queued<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+use crate::shared::*;
+
+#[cfg(test)]
+fn open_neighbours(cg: &CharGrid, pos: Position) -> Vec<Position> {
+    cg.neighbours(pos, ORTHOGONAL)
+        .filter(|(_, c)| **c != '#')
+        .map(|(p, _)| p)
+        .collect()
+}
+
+#[test]
+fn test_breadth_first() {
+    let cg = CharGrid::from_str("S.#.\n.##.\n....\n#..E\n").unwrap();
+    let start = cg.find_one_pos('S').unwrap();
+    let end = cg.find_one_pos('E').unwrap();
+
+    let search = breadth_first(start, |p| open_neighbours(&cg, *p), |p| *p == end, false);
+    assert_eq!(Some(&end), search.goal());
+    assert_eq!(Some(6), search.goal_distance());
+
+    let path = search.path().unwrap();
+    assert_eq!(7, path.len());
+    assert_eq!(start, path[0]);
+    assert_eq!(end, path[6]);
+
+    let search = breadth_first(start, |p| open_neighbours(&cg, *p), |_| false, false);
+    assert_eq!(None, search.goal());
+    assert_eq!(12, search.distances.len());
+    assert_eq!(Some(6), search.distance(&Position::at(3, 1)));
+    assert_eq!(None, search.path_to(&Position::at(2, 0)));
+}
+
+#[test]
+fn test_dijkstra() {
+    // Turning costs 1000 and a step forward costs 1, as on day 16.
+    let cg = CharGrid::from_str("#####\n#..E#\n#.#.#\n#S..#\n#####\n").unwrap();
+    let start = (cg.find_one_pos('S').unwrap(), RIGHT);
+    let end = cg.find_one_pos('E').unwrap();
+
+    let search = dijkstra(
+        start,
+        |(pos, dir)| {
+            let mut next = vec![((*pos, dir.clockwise()), 1000)];
+            next.push(((*pos, dir.anticlockwise()), 1000));
+            if cg.at_pos(*pos + *dir) != '#' {
+                next.push(((*pos + *dir, *dir), 1));
+            }
+            next
+        },
+        |(pos, _)| *pos == end,
+    );
+
+    assert_eq!(Some(1004), search.goal_distance());
+    assert_eq!(Some(&(end, UP)), search.goal());
+    assert_eq!(6, search.path().unwrap().len());
+}
+
+#[test]
+fn test_astar() {
+    let cg = CharGrid::from_str("S.#.\n.##.\n....\n#..E\n").unwrap();
+    let start = cg.find_one_pos('S').unwrap();
+    let end = cg.find_one_pos('E').unwrap();
+
+    let search = astar(
+        start,
+        |p| open_neighbours(&cg, *p).into_iter().map(|n| (n, 1)),
+        |p| ((end.x - p.x).abs() + (end.y - p.y).abs()) as usize,
+        |p| *p == end,
+    );
+
+    assert_eq!(Some(6), search.goal_distance());
+    assert_eq!(7, search.path().unwrap().len());
+    assert!(search.distance(&Position::at(3, 0)).is_none());
+}
+
+#[test]
+fn test_all_shortest_paths() {
+    let cg = CharGrid::from_str("S..\n.#.\n..E\n").unwrap();
+    let start = cg.find_one_pos('S').unwrap();
+    let end = cg.find_one_pos('E').unwrap();
+
+    let search = bfs_all(start, |p| open_neighbours(&cg, *p), |p| *p == end);
+    assert_eq!(Some(4), search.goal_distance());
+    assert_eq!(2, search.count_paths());
+    assert_eq!(2, search.predecessors(&end).len());
+    assert_eq!(8, search.on_any_path().len());
+
+    let mut preds = search.predecessors(&end).to_vec();
+    preds.sort_by_key(|p| p.x);
+    assert_eq!(vec![Position::at(1, 2), Position::at(2, 1)], preds);
+
+    // Without _all only the first path is kept.
+    let search = breadth_first(start, |p| open_neighbours(&cg, *p), |p| *p == end, false);
+    assert_eq!(1, search.count_paths());
+    assert_eq!(5, search.on_any_path().len());
+    assert_eq!(
+        search.path().unwrap().into_iter().collect::<HashSet<_>>(),
+        search.on_any_path()
+    );
+}
+
+#[test]
+fn test_all_goals() {
+    let cg = CharGrid::from_str("E.S.E\n..#..\n").unwrap();
+    let start = cg.find_one_pos('S').unwrap();
+
+    let search = dijkstra_all(
+        start,
+        |p| open_neighbours(&cg, *p).into_iter().map(|n| (n, 1)),
+        |p| cg.at_pos(*p) == 'E',
+    );
+    assert_eq!(2, search.goals().len());
+    assert_eq!(Some(2), search.goal_distance());
+    assert_eq!(2, search.count_paths());
+    assert_eq!(5, search.on_any_path().len());
+
+    let search = dijkstra(
+        start,
+        |p| open_neighbours(&cg, *p).into_iter().map(|n| (n, 1)),
+        |p| cg.at_pos(*p) == 'E',
+    );
+    assert_eq!(1, search.goals().len());
+}
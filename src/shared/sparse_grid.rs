@@ -0,0 +1,105 @@
+use crate::shared::*;
+use std::collections::HashMap;
+
+// A 2d grid with no fixed size, which only stores the cells which have been written. Any position
+// can be written, including negative ones, and the bounds grow to cover it. Cells which have not
+// been written read as the default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+    default: T,
+    // The top left and bottom right cells written so far.
+    bounds: Option<(Position, Position)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn get(&self, pos: Position) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    pub fn set(&mut self, pos: Position, v: T) {
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some((min, max)) => (
+                Position::at(min.x.min(pos.x), min.y.min(pos.y)),
+                Position::at(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+        });
+
+        self.cells.insert(pos, v);
+    }
+
+    // The top left cell and size of the area covering every cell written so far.
+    pub fn bounds(&self) -> Option<(Position, Dimensions)> {
+        self.bounds
+            .map(|(min, max)| (min, Dimensions::of(max.x - min.x + 1, max.y - min.y + 1)))
+    }
+
+    // The written cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    // Draws the area within the bounds, like the dense grids do.
+    pub fn draw_with(&self, f: impl Fn(&T) -> char) -> String {
+        let mut str = String::new();
+
+        if let Some((origin, dims)) = self.bounds() {
+            for y in 0..dims.h {
+                for x in 0..dims.w {
+                    str.push(f(self.get(origin.add_pos(Position::at(x, y)))))
+                }
+                str.push('\n')
+            }
+        }
+        str.push('\n');
+
+        str
+    }
+}
+
+impl SparseGrid<char> {
+    pub fn draw(&self) -> String {
+        self.draw_with(|c| *c)
+    }
+}
+
+#[test]
+fn test_sparse_grid() {
+    let mut grid = SparseGrid::new('.');
+    assert_eq!(None, grid.bounds());
+    assert_eq!("\n", grid.draw());
+
+    grid.set(Position::at(2, 1), '#');
+    grid.set(Position::at(-1, -2), 'S');
+    assert_eq!(2, grid.iter().count());
+    assert_eq!(&'S', grid.get(Position::at(-1, -2)));
+    assert_eq!(&'.', grid.get(Position::at(1000, -1000)));
+    assert_eq!(
+        Some((Position::at(-1, -2), Dimensions::of(4, 4))),
+        grid.bounds()
+    );
+    assert_eq!("S...\n....\n....\n...#\n\n", grid.draw());
+    assert_eq!(
+        CharGrid::from_str("S...\n....\n....\n...#\n")
+            .unwrap()
+            .draw(),
+        grid.draw()
+    );
+    assert_eq!(
+        "-...\n....\n....\n...+\n\n",
+        grid.draw_with(|c| match c {
+            '.' => '.',
+            'S' => '-',
+            _ => '+',
+        })
+    );
+}
@@ -0,0 +1,103 @@
+use crate::shared::*;
+
+// What happens at the edges of a grid: either they are the end of it, or stepping off one side
+// comes back in on the other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    #[default]
+    Bounded,
+    // Left and right edges are joined.
+    WrapX,
+    // Top and bottom edges are joined.
+    WrapY,
+    // Both.
+    Torus,
+}
+
+impl Topology {
+    pub fn wraps_x(&self) -> bool {
+        matches!(self, Topology::WrapX | Topology::Torus)
+    }
+
+    pub fn wraps_y(&self) -> bool {
+        matches!(self, Topology::WrapY | Topology::Torus)
+    }
+
+    // The same topology with the axes swapped, for rotated grids.
+    pub fn transposed(&self) -> Topology {
+        match self {
+            Topology::WrapX => Topology::WrapY,
+            Topology::WrapY => Topology::WrapX,
+            t => *t,
+        }
+    }
+
+    // Brings a position on a wrapping axis back within 0..dims, however far outside it is. None
+    // if the position is off a bounded edge.
+    pub fn wrap(&self, pos: Position, dims: Dimensions) -> Option<Position> {
+        let x = match self.wraps_x() {
+            true => pos.x.rem_euclid(dims.w),
+            false if pos.x >= 0 && pos.x < dims.w => pos.x,
+            false => return None,
+        };
+        let y = match self.wraps_y() {
+            true => pos.y.rem_euclid(dims.h),
+            false if pos.y >= 0 && pos.y < dims.h => pos.y,
+            false => return None,
+        };
+
+        Some(Position::at(x, y))
+    }
+}
+
+#[test]
+fn test_wrap() {
+    let dims = Dimensions::of(5, 3);
+
+    assert_eq!(None, Topology::Bounded.wrap(Position::at(5, 0), dims));
+    assert_eq!(
+        Some(Position::at(4, 2)),
+        Topology::Bounded.wrap(Position::at(4, 2), dims)
+    );
+
+    assert_eq!(
+        Some(Position::at(0, 1)),
+        Topology::WrapX.wrap(Position::at(5, 1), dims)
+    );
+    assert_eq!(None, Topology::WrapX.wrap(Position::at(5, -1), dims));
+    assert_eq!(
+        Some(Position::at(1, 2)),
+        Topology::WrapY.wrap(Position::at(1, -1), dims)
+    );
+
+    assert_eq!(
+        Some(Position::at(4, 1)),
+        Topology::Torus.wrap(Position::at(-1_000_001, 1_000_000), dims)
+    );
+}
+
+#[test]
+fn test_torus_steps() {
+    let step = |pos: Position, dir: Direction, dims: Dimensions| {
+        Topology::Torus.wrap(pos + dir, dims).unwrap()
+    };
+
+    let dims = Dimensions::of(10, 10);
+    assert_eq!(
+        Position::at(9, 9),
+        step(Position::at(0, 0), Direction::of(-1, -1), dims)
+    );
+    assert_eq!(
+        Position::at(5, 5),
+        step(Position::at(5, 5), Direction::of(-100, 100), dims)
+    );
+
+    let dims = Dimensions::of(11, 7);
+    let mut pos = Position::at(10, 3);
+    pos = step(pos, Direction::of(1, 2), dims);
+    assert_eq!(Position::at(0, 5), pos);
+    pos = step(pos, Direction::of(1, 2), dims);
+    assert_eq!(Position::at(1, 0), pos);
+    pos = step(pos, Direction::of(1, 2), dims);
+    assert_eq!(Position::at(2, 2), pos);
+}
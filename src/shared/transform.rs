@@ -0,0 +1,103 @@
+use crate::shared::*;
+
+// Geometric transforms, each of which returns a new grid.
+impl<T: Clone> Grid<T> {
+    fn cell(&self, x: i32, y: i32) -> T {
+        self.get(Position::at(x, y)).unwrap().clone()
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        Grid::from_fn(self.height(), self.width(), |x, y| {
+            self.cell(y, self.y_max() - x)
+        })
+        .with_topology(self.topology().transposed())
+    }
+
+    // Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Grid::from_fn(self.width(), self.height(), |x, y| {
+            self.cell(self.x_max() - x, y)
+        })
+        .with_topology(self.topology())
+    }
+
+    // Replaces every cell with a block of width x height cells, given row by row by f.
+    pub fn expand<U: Clone>(
+        &self,
+        width: i32,
+        height: i32,
+        mut f: impl FnMut(&T) -> Result<Vec<U>>,
+    ) -> Result<Grid<U>> {
+        let mut blocks = Vec::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let block = f(&self.cell(x, y))?;
+                if block.len() != (width * height) as usize {
+                    return Err(Error::new(&format!(
+                        "expanded cell at ({}, {}) to {} cells rather than {}",
+                        x,
+                        y,
+                        block.len(),
+                        width * height
+                    )));
+                }
+                blocks.push(block);
+            }
+        }
+
+        Ok(
+            Grid::from_fn(self.width() * width, self.height() * height, |x, y| {
+                let block = &blocks[((y / height) * self.width() + x / width) as usize];
+                block[((y % height) * width + x % width) as usize].clone()
+            })
+            .with_topology(self.topology()),
+        )
+    }
+}
+
+#[cfg(test)]
+fn example() -> CharGrid {
+    CharGrid::from_str("abc\ndef\n").unwrap()
+}
+
+#[test]
+fn test_rotate_and_flip() {
+    let cg = example();
+
+    assert_eq!("da\neb\nfc\n\n", cg.rotate_clockwise().draw());
+    assert_eq!("cba\nfed\n\n", cg.flip_horizontal().draw());
+    assert_eq!(cg, cg.flip_horizontal().flip_horizontal());
+    assert_eq!(
+        cg,
+        cg.rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+    );
+
+    let wrapping = cg.with_topology(Topology::WrapX);
+    assert_eq!(Topology::WrapY, wrapping.rotate_clockwise().topology());
+    assert_eq!(Topology::WrapX, wrapping.flip_horizontal().topology());
+}
+
+#[test]
+fn test_expand() {
+    let cg = CharGrid::from_str("#O\n").unwrap();
+
+    let wide = cg
+        .expand(2, 1, |c| match c {
+            '#' => Ok(vec!['#', '#']),
+            'O' => Ok(vec!['[', ']']),
+            _ => Err(Error::new("unexpected char")),
+        })
+        .unwrap();
+    assert_eq!("##[]\n\n", wide.draw());
+
+    let scaled = cg.expand(2, 2, |c| Ok(vec![*c; 4])).unwrap();
+    assert_eq!("##OO\n##OO\n\n", scaled.draw());
+
+    assert!(cg.expand(2, 1, |c| Ok(vec![*c])).is_err());
+    assert!(example()
+        .expand(1, 1, |_| Err::<Vec<char>, _>(Error::new("x")))
+        .is_err());
+}